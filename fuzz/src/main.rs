@@ -70,6 +70,11 @@ fn main() {
         let ss = b4s::SortedString::new_unchecked(&data.haystack, data.separator);
 
         let _ = ss.binary_search(&data.needle);
+        let _ = ss.entries_with_prefix(&data.needle).count();
+        let _ = ss.fuzzy_search(&data.needle, 2, b4s::EditDistance::Damerau);
+        if let Ok(glob) = b4s::Glob::new(&data.needle) {
+            let _ = ss.entries_matching(&glob).count();
+        }
 
         // It's not easy to get insight into the input data unless a panic occurred, so
         // write it out. This costs some performance.
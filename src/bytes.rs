@@ -0,0 +1,148 @@
+//! Haystacks of raw bytes, which need not be valid UTF-8.
+
+use crate::{bisect, entry_around, SearchResult, Side, SortedStringCreationError, Tile};
+use itertools::Itertools;
+use std::{fmt::Display, ops::Range};
+
+/// The counterpart to [`SortedString`](crate::SortedString) for haystacks of raw bytes.
+///
+/// Useful for data which is not valid UTF-8, such as hashes or legacy files in
+/// [Latin-1](https://en.wikipedia.org/wiki/ISO/IEC_8859-1). Entries are separated by a
+/// single byte, which can be *any* byte, and are compared bytewise, as slices are.
+/// Creation, validation and searching otherwise work just like for
+/// [`SortedString`](crate::SortedString). Haystacks which turn out to be valid UTF-8
+/// can be converted using
+/// [`SortedString::from_utf8()`](crate::SortedString::from_utf8).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::SortedBytes;
+///
+/// // "Apfel", "Äpfel" and "Öl" in Latin-1
+/// let haystack = b"Apfel\n\xC4pfel\n\xD6l";
+/// assert!(std::str::from_utf8(haystack).is_err());
+///
+/// let sb = SortedBytes::new_checked(haystack, b'\n')?;
+/// assert_eq!(
+///     sb.binary_search(b"\xC4pfel"),
+///     Ok(std::ops::Range { start: 6, end: 11 })
+/// );
+/// assert!(sb.binary_search("Äpfel").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortedBytes<'a> {
+    bytes: &'a [u8],
+    sep: u8,
+}
+
+impl<'a> SortedBytes<'a> {
+    /// Creates a new instance of [`SortedBytes`], performing sanity checks.
+    ///
+    /// See [`SortedString::new_checked()`](crate::SortedString::new_checked).
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SortedString::new_checked()`](crate::SortedString::new_checked).
+    pub fn new_checked(haystack: &'a [u8], sep: u8) -> Result<Self, SortedStringCreationError> {
+        if haystack.is_empty() {
+            return Err(SortedStringCreationError::EmptyHaystack);
+        }
+
+        let sorted_bytes = Self::new_unchecked(haystack, sep);
+
+        if sorted_bytes.iter().tuple_windows().all(|(a, b)| a <= b) {
+            Ok(sorted_bytes)
+        } else {
+            Err(SortedStringCreationError::NotSorted)
+        }
+    }
+
+    /// Creates an instance of [`SortedBytes`] without performing sanity checks.
+    ///
+    /// See [`SortedString::new_unchecked()`](crate::SortedString::new_unchecked).
+    #[must_use]
+    pub const fn new_unchecked(haystack: &'a [u8], sep: u8) -> Self {
+        Self {
+            bytes: haystack,
+            sep,
+        }
+    }
+
+    /// Convenience method to sort bytes by a given separator, returning an owned
+    /// version.
+    ///
+    /// See [`SortedString::sort()`](crate::SortedString::sort).
+    #[must_use]
+    pub fn sort(haystack: &[u8], sep: u8) -> Vec<u8> {
+        haystack
+            .split(|byte| *byte == sep)
+            .sorted()
+            .collect::<Vec<&[u8]>>()
+            .join(&sep)
+    }
+
+    /// Searches for a needle, see
+    /// [`SortedString::binary_search()`](crate::SortedString::binary_search).
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SearchError`](crate::SearchError) for more info.
+    pub fn binary_search<U>(&self, needle: U) -> SearchResult
+    where
+        U: AsRef<[u8]>,
+    {
+        let needle = needle.as_ref();
+        let sep = std::slice::from_ref(&self.sep);
+
+        let tile_around = |pos| {
+            let entry = entry_around(self.bytes, sep, None, pos);
+            Tile {
+                tiles: entry.start..entry.end + sep.len(),
+                entry,
+            }
+        };
+
+        bisect(
+            self.bytes.len(),
+            tile_around,
+            |entry| self.bytes[entry].cmp(needle),
+            0..self.bytes.len() + sep.len(),
+            Range { start: 0, end: 0 },
+            Side::Left,
+        )
+    }
+
+    /// Returns an iterator over all entries, in sorted order.
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'a [u8]> {
+        let sep = self.sep;
+        self.bytes.split(move |byte| *byte == sep)
+    }
+
+    /// Returns the haystack.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the separator.
+    #[must_use]
+    pub const fn separator(&self) -> u8 {
+        self.sep
+    }
+}
+
+impl Display for SortedBytes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SortedBytes(\"{}\", '{}')",
+            self.bytes.escape_ascii(),
+            self.sep.escape_ascii()
+        )
+    }
+}
@@ -0,0 +1,225 @@
+//! Locale-aware orderings, as produced by office tools and `sort` with a locale set.
+
+use crate::SortOrder;
+use itertools::Either;
+use std::{cmp::Ordering, iter::once};
+use unicode_normalization::UnicodeNormalization;
+
+/// Primary weights of characters, mapped to by a tailoring.
+type Tailoring = &'static [(char, &'static [u32])];
+
+/// Orders entries as humans of a given locale expect, similar to [ICU
+/// collation](https://unicode-org.github.io/icu/userguide/collation/).
+///
+/// Entries are compared on multiple levels, each only breaking ties of the previous:
+///
+/// 1. base letters, ignoring accents and case, with letters ordered as in the locale's
+///    alphabet (in Swedish, `å`, `ä` and `ö` come after `z`, for example)
+/// 2. accents, with unaccented letters first
+/// 3. case, with lowercase letters first
+/// 4. bytes, so that only identical entries compare equal
+///
+/// Spaces and punctuation sort before digits, which sort before letters. Locales are
+/// defined by small tailoring tables shipped with this crate, no system libraries are
+/// required. Contractions (e.g. Danish `aa` sorting like `å`) are not supported.
+///
+/// Requires the `unicode` feature.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, Collation, SortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+///
+/// let unsorted_haystack = "Zaun\näpfel\nApfel\nÄpfel\napfel";
+/// let haystack =
+///     SortedString::sort_with_order(unsorted_haystack, sep, &Collation::GERMAN);
+/// assert_eq!(haystack, "apfel\nApfel\näpfel\nÄpfel\nZaun");
+///
+/// let unsorted_haystack = "Öl\nZebra\nÄpple\nApa\nÅsa";
+/// let haystack =
+///     SortedString::sort_with_order(unsorted_haystack, sep, &Collation::SWEDISH);
+/// assert_eq!(haystack, "Apa\nZebra\nÅsa\nÄpple\nÖl");
+///
+/// let ss = SortedString::new_checked_with_order(&haystack, sep, Collation::SWEDISH)?;
+/// assert!(ss.binary_search("Äpple").is_ok());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Collation {
+    tailoring: Tailoring,
+}
+
+impl Collation {
+    /// The locale-independent default ordering, suitable for English, for example.
+    pub const ROOT: Self = Self { tailoring: &[] };
+
+    /// German dictionary order ([DIN 5007-1]): umlauts sort like their base letters,
+    /// `ß` like `ss`.
+    ///
+    /// This is exactly the [root order](Collation::ROOT), which this is an alias of.
+    ///
+    /// [DIN 5007-1]: https://de.wikipedia.org/wiki/Alphabetische_Sortierung#Deutschland
+    pub const GERMAN: Self = Self::ROOT;
+
+    /// German phone book order (DIN 5007-2): umlauts sort like their base letters
+    /// followed by `e`, so `Müller` sorts like `Mueller`.
+    pub const GERMAN_PHONEBOOK: Self = Self {
+        tailoring: &[
+            ('ä', &[letter('a'), letter('e')]),
+            ('ö', &[letter('o'), letter('e')]),
+            ('ü', &[letter('u'), letter('e')]),
+        ],
+    };
+
+    /// Swedish order: `å`, `ä` and `ö` are letters of their own, following `z`.
+    pub const SWEDISH: Self = Self {
+        tailoring: &[
+            ('å', &[after('z', 1)]),
+            ('ä', &[after('z', 2)]),
+            ('æ', &[after('z', 2)]),
+            ('ö', &[after('z', 3)]),
+            ('ø', &[after('z', 3)]),
+            ('ü', &[letter('y')]),
+        ],
+    };
+
+    /// Finnish order, which is the same as [`Collation::SWEDISH`].
+    pub const FINNISH: Self = Self::SWEDISH;
+
+    /// Danish order: `æ`, `ø` and `å` are letters of their own, following `z`.
+    pub const DANISH: Self = Self {
+        tailoring: &[
+            ('æ', &[after('z', 1)]),
+            ('ä', &[after('z', 1)]),
+            ('ø', &[after('z', 2)]),
+            ('ö', &[after('z', 2)]),
+            ('å', &[after('z', 3)]),
+            ('ü', &[letter('y')]),
+        ],
+    };
+
+    /// Norwegian order, which is the same as [`Collation::DANISH`].
+    pub const NORWEGIAN: Self = Self::DANISH;
+
+    /// Spanish order: `ñ` is a letter of its own, following `n`.
+    pub const SPANISH: Self = Self {
+        tailoring: &[('ñ', &[after('n', 1)])],
+    };
+
+    /// Applies to all locales, unless overridden by their tailoring.
+    const BASE_TAILORING: Tailoring = &[
+        ('ß', &[letter('s'), letter('s')]),
+        ('æ', &[letter('a'), letter('e')]),
+        ('œ', &[letter('o'), letter('e')]),
+        ('ø', &[letter('o')]),
+        ('đ', &[letter('d')]),
+        ('ł', &[letter('l')]),
+        ('ı', &[letter('i')]),
+    ];
+
+    fn elements(self, s: &str) -> impl Iterator<Item = Element> + '_ {
+        s.chars().nfc().flat_map(move |c| self.elements_of(c))
+    }
+
+    fn elements_of(self, c: char) -> impl Iterator<Item = Element> {
+        let uppercase = c.is_uppercase();
+        let lower = if uppercase {
+            c.to_lowercase().next().unwrap_or(c)
+        } else {
+            c
+        };
+
+        let tailored = self
+            .tailoring
+            .iter()
+            .chain(Self::BASE_TAILORING)
+            .find(|(from, _)| *from == lower);
+
+        if let Some((_, weights)) = tailored {
+            return Either::Left(weights.iter().map(move |&primary| Element {
+                primary,
+                secondary: 0,
+                uppercase,
+            }));
+        }
+
+        let mut decomposed = once(lower).nfd();
+        let base = decomposed.next().unwrap_or(lower);
+        let mark = decomposed.next();
+
+        let element = if unicode_normalization::char::is_combining_mark(base) {
+            // Leftover mark not composable with anything, so only an accent.
+            Element {
+                primary: 0,
+                secondary: u32::from(base),
+                uppercase,
+            }
+        } else {
+            Element {
+                primary: primary_weight(base),
+                secondary: mark.map_or(0, u32::from),
+                uppercase,
+            }
+        };
+
+        Either::Right(once(element))
+    }
+}
+
+impl SortOrder for Collation {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        let primary = |s| self.elements(s).map(|e| e.primary).filter(|p| *p != 0);
+        let secondary = |s| self.elements(s).map(|e| e.secondary);
+        let tertiary = |s| self.elements(s).map(|e| e.uppercase);
+
+        primary(a)
+            .cmp(primary(b))
+            .then_with(|| secondary(a).cmp(secondary(b)))
+            .then_with(|| tertiary(a).cmp(tertiary(b)))
+            .then_with(|| a.cmp(b))
+    }
+}
+
+/// The collation weights of a single [`char`].
+#[derive(Debug, Clone, Copy)]
+struct Element {
+    /// Base letter, where zero means ignorable.
+    primary: u32,
+    /// Accent, where zero means unaccented.
+    secondary: u32,
+    uppercase: bool,
+}
+
+/// Weights of spaces and punctuation, which sort first.
+const PUNCTUATION: u32 = 1 << 28;
+/// Weights of digits, which sort after punctuation.
+const DIGIT: u32 = 2 << 28;
+/// Weights of letters, which sort last.
+const LETTER: u32 = 3 << 28;
+/// Tailored weights can be slotted in after an existing one, up to this many.
+const SLOTS: u32 = 1 << 4;
+
+const fn letter(c: char) -> u32 {
+    LETTER | (c as u32 * SLOTS)
+}
+
+/// Weight of the `n`th letter of a locale following `c` in its alphabet.
+const fn after(c: char, n: u32) -> u32 {
+    letter(c) + n
+}
+
+fn primary_weight(c: char) -> u32 {
+    let group = if c.is_alphabetic() {
+        LETTER
+    } else if c.is_numeric() {
+        DIGIT
+    } else {
+        PUNCTUATION
+    };
+
+    group | (u32::from(c) * SLOTS)
+}
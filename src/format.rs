@@ -0,0 +1,337 @@
+//! How entries are laid out in a haystack, beyond the separator between them.
+
+use ascii::AsciiChar;
+use std::{borrow::Cow, ops::Range};
+
+/// Options for reading entries from a haystack, such as a text file.
+///
+/// By default, every separator separates two entries, so a haystack ending in a
+/// separator has an empty last entry. That is inconvenient for text files, which
+/// usually end in a line feed. Options apply to validation, iteration and all searches
+/// alike. Entries ignored by them are never yielded nor found, but their bytes still
+/// count towards byte offsets, so those keep pointing into the original haystack.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, Bytewise, Format, SortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+/// let haystack = "Aachen\nAmpel\nApfel\n";
+/// assert!(SortedString::new_checked(haystack, sep).is_err());
+///
+/// let format = Format::new().with_trailing_separator();
+/// let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format)?;
+/// assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["Aachen", "Ampel", "Apfel"]);
+///
+/// // Blank lines anywhere:
+/// let haystack = "\nAachen\n\nAmpel\n\n";
+/// let format = Format::new().skipping_empty_entries();
+/// let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format)?;
+/// assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["Aachen", "Ampel"]);
+/// assert_eq!(ss.binary_search("Ampel"), Ok(std::ops::Range { start: 9, end: 14 }));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Dictionary Files
+///
+/// Word lists often contain comment lines, headers and entries padded with spaces or
+/// tabs. [`Format::dictionary()`] deals with all of these at once, and
+/// [`SortedString::sort_with_format()`](crate::SortedString::sort_with_format)
+/// produces the cleaned up form:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, Bytewise, Format, SortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+/// let haystack = "# Fruit, sorted\n\nApfel \n  Birne\n# Stone fruit\nKirsche\t\n";
+/// let format = Format::dictionary();
+///
+/// let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format)?;
+/// assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["Apfel", "Birne", "Kirsche"]);
+/// assert_eq!(ss.binary_search("Birne"), Ok(std::ops::Range { start: 26, end: 31 }));
+///
+/// let cleaned = SortedString::sort_with_format(haystack, sep, &Bytewise, format);
+/// assert_eq!(cleaned, "Apfel\nBirne\nKirsche\n");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Escaped Separators
+///
+/// Entries can contain the separator if it is escaped, see [`Format::with_escape()`]:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, Bytewise, Format, SortedString};
+///
+/// let haystack = r"Hello,Hello\, World,Tschüss";
+/// let format = Format::new().with_escape(AsciiChar::BackSlash);
+/// let sep = AsciiChar::Comma;
+/// let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format)?;
+///
+/// let entries = ss.iter().collect::<Vec<_>>();
+/// assert_eq!(entries, vec!["Hello", r"Hello\, World", "Tschüss"]);
+///
+/// let found = ss.search("Hello, World")?;
+/// assert_eq!(found.entry, r"Hello\, World");
+/// assert_eq!(ss.unescape(found.entry), "Hello, World");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Format {
+    trailing_separator: bool,
+    skip_empty: bool,
+    trim: bool,
+    comment: Option<char>,
+    escape: Option<u8>,
+}
+
+impl Format {
+    /// Creates the default format, where every separator separates two entries.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            trailing_separator: false,
+            skip_empty: false,
+            trim: false,
+            comment: None,
+            escape: None,
+        }
+    }
+
+    /// Creates a format for dictionary files, such as word lists.
+    ///
+    /// Ignores a trailing separator, blank entries and comment entries starting with
+    /// `#`, and trims whitespace off entries.
+    #[must_use]
+    pub const fn dictionary() -> Self {
+        Self::new()
+            .with_trailing_separator()
+            .skipping_empty_entries()
+            .trimming_whitespace()
+            .with_comments('#')
+    }
+
+    /// Ignores a single separator at the very end of the haystack, instead of treating
+    /// it as followed by an empty entry.
+    #[must_use]
+    pub const fn with_trailing_separator(self) -> Self {
+        Self {
+            trailing_separator: true,
+            ..self
+        }
+    }
+
+    /// Ignores empty entries anywhere in the haystack, such as blank lines.
+    ///
+    /// A trailing separator is then ignored as well, as it is followed by an empty
+    /// entry.
+    #[must_use]
+    pub const fn skipping_empty_entries(self) -> Self {
+        Self {
+            skip_empty: true,
+            ..self
+        }
+    }
+
+    /// Removes leading and trailing [whitespace](char::is_whitespace) from entries.
+    ///
+    /// Entries consisting only of whitespace become empty, and are ignored if
+    /// [`Format::skipping_empty_entries()`] is set as well.
+    #[must_use]
+    pub const fn trimming_whitespace(self) -> Self {
+        Self { trim: true, ..self }
+    }
+
+    /// Ignores entries starting with `marker`, such as `#`.
+    ///
+    /// If [`Format::trimming_whitespace()`] is set as well, leading whitespace before
+    /// `marker` is allowed.
+    #[must_use]
+    pub const fn with_comments(self, marker: char) -> Self {
+        Self {
+            comment: Some(marker),
+            ..self
+        }
+    }
+
+    /// Treats separators preceded by `escape` as part of entries.
+    ///
+    /// To end an entry in `escape`, it has to be escaped itself, e.g. `\\,` for an
+    /// entry ending in a backslash, followed by a comma separator. Any other use of
+    /// `escape` is taken literally. `escape` must not be part of the separator, see
+    /// [`SortedStringCreationError::EscapeInSeparator`](crate::SortedStringCreationError::EscapeInSeparator).
+    ///
+    /// Entries are validated, sorted and searched for in their unescaped form, so
+    /// needles must not be escaped. Queries yield entries as they appear in the
+    /// haystack; see [`SortedString::unescape()`](crate::SortedString::unescape) for
+    /// their unescaped form. Closures passed to methods such as
+    /// [`SortedString::binary_search_by()`](crate::SortedString::binary_search_by)
+    /// receive entries as they appear in the haystack as well.
+    #[must_use]
+    pub const fn with_escape(self, escape: AsciiChar) -> Self {
+        Self {
+            escape: Some(escape.as_byte()),
+            ..self
+        }
+    }
+
+    /// Whether any entries might be ignored, besides the one following a trailing
+    /// separator.
+    pub(crate) const fn skips_entries(self) -> bool {
+        self.skip_empty || self.comment.is_some()
+    }
+
+    /// Whether a single separator at the end of a haystack is ignored.
+    pub(crate) const fn has_trailing_separator(self) -> bool {
+        self.trailing_separator
+    }
+
+    /// The escape byte, if any, see [`Format::with_escape()`].
+    pub(crate) const fn escape_byte(self) -> Option<u8> {
+        self.escape
+    }
+
+    /// Returns the escape, if `entry` ends in one that is not escaped itself.
+    pub(crate) fn dangling_escape(self, entry: &str) -> Option<char> {
+        self.escape
+            .filter(|_| is_escaped(entry.as_bytes(), self.escape, entry.len()))
+            .map(char::from)
+    }
+
+    /// Whether the escape byte, if any, is part of `sep`.
+    pub(crate) fn escapes_within(self, sep: &str) -> bool {
+        self.escape
+            .map_or(false, |escape| sep.as_bytes().contains(&escape))
+    }
+
+    /// Whether a separator at the end of `haystack` is ignored.
+    pub(crate) fn ignores_trailing(self, haystack: &str, sep: &str) -> bool {
+        self.trailing_separator
+            && haystack.len() >= sep.len()
+            && crate::splits_at(
+                haystack.as_bytes(),
+                sep.as_bytes(),
+                self.escape,
+                haystack.len() - sep.len(),
+            )
+    }
+
+    /// Returns `entry`, as it appears in the haystack, in its unescaped form.
+    pub(crate) fn unescape<'e>(self, entry: &'e str, sep: &str) -> Cow<'e, str> {
+        let escape = match self.escape {
+            Some(escape) if entry.as_bytes().contains(&escape) => char::from(escape),
+            _ => return Cow::Borrowed(entry),
+        };
+
+        let mut unescaped = String::with_capacity(entry.len());
+        let mut rest = entry;
+
+        while let Some(i) = rest.find(escape) {
+            unescaped.push_str(&rest[..i]);
+            let after = &rest[i + 1..];
+
+            rest = if let Some(after) = after.strip_prefix(escape) {
+                unescaped.push(escape);
+                after
+            } else if let Some(after) = after.strip_prefix(sep) {
+                unescaped.push_str(sep);
+                after
+            } else {
+                unescaped.push(escape);
+                after
+            };
+        }
+
+        unescaped.push_str(rest);
+        Cow::Owned(unescaped)
+    }
+
+    /// Returns the offsets into `entry`, as it appears in the haystack, at which its
+    /// unescaped [`char`]s start.
+    ///
+    /// Unescaping `entry` from any of these offsets yields the corresponding suffix of
+    /// the unescaped entry.
+    pub(crate) fn char_offsets(self, entry: &str, sep: &str) -> Vec<usize> {
+        let escape = match self.escape {
+            Some(escape) => char::from(escape),
+            None => return entry.char_indices().map(|(i, _)| i).collect(),
+        };
+
+        let mut offsets = Vec::with_capacity(entry.len());
+        let mut i = 0;
+
+        while let Some(c) = entry[i..].chars().next() {
+            offsets.push(i);
+            let after = &entry[i + c.len_utf8()..];
+
+            i += if c == escape && after.starts_with(escape) {
+                2 * c.len_utf8()
+            } else if c == escape && after.starts_with(sep) {
+                // The escape stands in for the separator's first `char`, all others
+                // start where they are.
+                let sep_start = i + c.len_utf8();
+                offsets.extend(sep.char_indices().skip(1).map(|(j, _)| sep_start + j));
+                c.len_utf8() + sep.len()
+            } else {
+                c.len_utf8()
+            };
+        }
+
+        offsets
+    }
+
+    /// Returns the location of the entry at `raw`, the bytes of `haystack` between two
+    /// separators, or [`None`] if it is ignored.
+    pub(crate) fn entry(
+        self,
+        haystack: &str,
+        sep: &str,
+        raw: Range<usize>,
+    ) -> Option<Range<usize>> {
+        let mut entry = &haystack[raw.clone()];
+        let mut start = raw.start;
+
+        if self.trim {
+            let trimmed = entry.trim_start();
+            start += entry.len() - trimmed.len();
+
+            // Escaped separators are content, even if they are whitespace.
+            let mut end = trimmed.trim_end().len();
+            if is_escaped(haystack.as_bytes(), self.escape, start + end)
+                && trimmed[end..].starts_with(sep)
+            {
+                end += sep.len();
+            }
+            entry = &trimmed[..end];
+        }
+
+        let is_comment = self
+            .comment
+            .map_or(false, |marker| entry.starts_with(marker));
+        if is_comment || (self.skip_empty && entry.is_empty()) {
+            None
+        } else {
+            Some(start..start + entry.len())
+        }
+    }
+}
+
+/// Returns whether the byte at `i` is escaped, i.e. preceded by an odd number of
+/// `escape` bytes.
+pub(crate) fn is_escaped(haystack: &[u8], escape: Option<u8>, i: usize) -> bool {
+    escape.map_or(false, |escape| {
+        haystack[..i]
+            .iter()
+            .rev()
+            .take_while(|byte| **byte == escape)
+            .count()
+            % 2
+            == 1
+    })
+}
@@ -0,0 +1,148 @@
+//! Approximate search, finding entries within an edit distance of a needle.
+
+use std::{fmt::Display, ops::Range};
+
+/// A measure of how different two strings are, in single-[`char`] edits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EditDistance {
+    /// [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance):
+    /// insertions, deletions and substitutions.
+    #[default]
+    Levenshtein,
+    /// [Damerau-Levenshtein
+    /// distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance):
+    /// additionally counts transposing two adjacent [`char`]s as a single edit.
+    ///
+    /// This is the *optimal string alignment* variant, which does not edit a substring
+    /// more than once.
+    Damerau,
+}
+
+/// An entry found by
+/// [`SortedString::fuzzy_search()`](crate::SortedString::fuzzy_search).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FuzzyMatch<'a> {
+    /// The location of the entry in the haystack.
+    pub range: Range<usize>,
+    /// The entry itself, a slice of the haystack.
+    pub entry: &'a str,
+    /// The edit distance between the entry and the needle.
+    pub distance: usize,
+}
+
+impl AsRef<str> for FuzzyMatch<'_> {
+    fn as_ref(&self) -> &str {
+        self.entry
+    }
+}
+
+impl Display for FuzzyMatch<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.entry)
+    }
+}
+
+/// Outcome of feeding an entry to [`Rows`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Feed {
+    /// The entry is within the maximum distance.
+    Within(usize),
+    /// The entry exceeds the maximum distance.
+    Beyond,
+    /// The entry's first `prefix_len` bytes already exceed the maximum distance, as do
+    /// all entries starting with them.
+    Dead { prefix_len: usize },
+}
+
+/// Edit distance matrix of a needle against a sequence of entries.
+///
+/// Each row holds the distances of the needle's prefixes to one prefix of the current
+/// entry. Rows are stacked, so feeding entries in sorted order only computes rows for
+/// the part of an entry not shared with its predecessor.
+#[derive(Debug)]
+pub(crate) struct Rows {
+    needle: Vec<char>,
+    max_distance: usize,
+    metric: EditDistance,
+    /// The current entry's [`char`]s, one per row but the first.
+    chars: Vec<char>,
+    /// All rows, flattened.
+    matrix: Vec<usize>,
+}
+
+impl Rows {
+    pub(crate) fn new(needle: &str, max_distance: usize, metric: EditDistance) -> Self {
+        let needle: Vec<char> = needle.chars().collect();
+        let matrix = (0..=needle.len()).collect();
+
+        Self {
+            needle,
+            max_distance,
+            metric,
+            chars: Vec::new(),
+            matrix,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.needle.len() + 1
+    }
+
+    pub(crate) fn feed(&mut self, entry: &str) -> Feed {
+        let common = self
+            .chars
+            .iter()
+            .zip(entry.chars())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        self.chars.truncate(common);
+        self.matrix.truncate((common + 1) * self.width());
+
+        for (i, c) in entry.char_indices().skip(common) {
+            self.chars.push(c);
+
+            if self.push_row() > self.max_distance {
+                return Feed::Dead {
+                    prefix_len: i + c.len_utf8(),
+                };
+            }
+        }
+
+        match self.matrix.last() {
+            Some(&distance) if distance <= self.max_distance => Feed::Within(distance),
+            _ => Feed::Beyond,
+        }
+    }
+
+    /// Computes the row for the last of [`Rows::chars`], returning its minimum.
+    fn push_row(&mut self) -> usize {
+        let width = self.width();
+        let i = self.chars.len();
+        let c = self.chars[i - 1];
+        let prev = self.matrix.len() - width;
+
+        self.matrix.push(i);
+        let mut min = i;
+
+        for j in 1..width {
+            let substitution = usize::from(self.needle[j - 1] != c);
+            let mut distance = (self.matrix[prev + j] + 1)
+                .min(self.matrix[prev + width + j - 1] + 1)
+                .min(self.matrix[prev + j - 1] + substitution);
+
+            if self.metric == EditDistance::Damerau
+                && i > 1
+                && j > 1
+                && c == self.needle[j - 2]
+                && self.chars[i - 2] == self.needle[j - 1]
+            {
+                distance = distance.min(self.matrix[prev - width + j - 2] + 1);
+            }
+
+            self.matrix.push(distance);
+            min = min.min(distance);
+        }
+
+        min
+    }
+}
@@ -0,0 +1,239 @@
+//! Wildcard patterns, as known from shells.
+
+use crate::Entries;
+use std::{error::Error, fmt::Display, iter::FusedIterator};
+
+/// A wildcard pattern, to find entries with
+/// [`SortedString::entries_matching()`](crate::SortedString::entries_matching).
+///
+/// The syntax is that of shell globs:
+///
+/// - `?` matches any single [`char`]
+/// - `*` matches any sequence of [`char`]s, including none
+/// - `[bc]` matches any of the listed [`char`]s, `[a-c]` any in the range, and `[!bc]`
+///   (or `[^bc]`) any but the listed ones; a `]` right after the opening bracket is
+///   listed literally
+/// - `\` escapes the following [`char`], matching it literally, also within classes
+/// - any other [`char`] matches itself
+///
+/// Patterns match entire entries, not substrings: use `*foo*` to find entries
+/// containing `foo`. Matching is case-sensitive.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::Glob;
+///
+/// let glob = Glob::new("re*ing")?;
+/// assert!(glob.is_match("rewriting"));
+/// assert!(!glob.is_match("rewrite"));
+///
+/// assert_eq!(Glob::new("[bc"), Err(b4s::GlobError::UnclosedClass { start: 0 }));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Glob {
+    tokens: Vec<Token>,
+    literal_prefix: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Token {
+    Literal(char),
+    Any,
+    Star,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Literal(l) => *l == c,
+            Self::Any => true,
+            Self::Star => unreachable!("stars are handled by backtracking"),
+            Self::Class { negated, ranges } => {
+                ranges.iter().any(|(low, high)| (*low..=*high).contains(&c)) != *negated
+            }
+        }
+    }
+}
+
+impl Glob {
+    /// Parses a wildcard pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GlobError`] if the pattern is malformed.
+    pub fn new(pattern: &str) -> Result<Self, GlobError> {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let token = match c {
+                '?' => Token::Any,
+                '*' => Token::Star,
+                '\\' => Token::Literal(chars.next().ok_or(GlobError::DanglingEscape)?.1),
+                '[' => {
+                    let negated = chars.next_if(|(_, c)| matches!(c, '!' | '^')).is_some();
+                    let mut ranges = Vec::new();
+
+                    loop {
+                        let low = match chars.next() {
+                            Some((_, ']')) if !ranges.is_empty() => break,
+                            Some((_, '\\')) => chars.next(),
+                            other => other,
+                        };
+                        let low = low.ok_or(GlobError::UnclosedClass { start: i })?.1;
+
+                        let high = match chars.next_if(|(_, c)| *c == '-') {
+                            // A trailing `-` is listed literally.
+                            Some(dash) if chars.peek().map(|(_, c)| *c) == Some(']') => {
+                                ranges.push((dash.1, dash.1));
+                                low
+                            }
+                            Some(_) => {
+                                let high = match chars.next() {
+                                    Some((_, '\\')) => chars.next(),
+                                    other => other,
+                                };
+                                high.ok_or(GlobError::UnclosedClass { start: i })?.1
+                            }
+                            None => low,
+                        };
+
+                        ranges.push((low, high));
+                    }
+
+                    Token::Class { negated, ranges }
+                }
+                c => Token::Literal(c),
+            };
+
+            tokens.push(token);
+        }
+
+        let literal_prefix = tokens
+            .iter()
+            .map_while(|token| match token {
+                Token::Literal(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Self {
+            tokens,
+            literal_prefix,
+        })
+    }
+
+    /// Returns whether the pattern matches all of `s`.
+    #[must_use]
+    pub fn is_match(&self, s: &str) -> bool {
+        let mut token = 0;
+        let mut pos = 0;
+        // Where to resume if matching fails: the token after the last star, and the
+        // position in `s` it is currently tried at.
+        let mut backtrack = None;
+
+        loop {
+            match self.tokens.get(token) {
+                Some(Token::Star) => {
+                    token += 1;
+                    backtrack = Some((token, pos));
+                    continue;
+                }
+                Some(t) => {
+                    if let Some(c) = s[pos..].chars().next() {
+                        if t.matches(c) {
+                            token += 1;
+                            pos += c.len_utf8();
+                            continue;
+                        }
+                    }
+                }
+                None if pos == s.len() => return true,
+                None => {}
+            }
+
+            // Let the last star swallow one more `char`, and retry from there.
+            match backtrack {
+                Some((star_token, star_pos)) if star_pos < s.len() => {
+                    let c = s[star_pos..].chars().next().unwrap_or_default();
+                    token = star_token;
+                    pos = star_pos + c.len_utf8();
+                    backtrack = Some((token, pos));
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    /// The [`char`]s all matching entries start with.
+    pub(crate) fn literal_prefix(&self) -> &str {
+        &self.literal_prefix
+    }
+}
+
+/// Iterator over all entries matching a [`Glob`], see
+/// [`SortedString::entries_matching()`](crate::SortedString::entries_matching).
+#[derive(Debug, Clone)]
+pub struct GlobMatches<'a, 'g> {
+    pub(crate) entries: Entries<'a>,
+    pub(crate) glob: &'g Glob,
+}
+
+impl<'a> Iterator for GlobMatches<'a, '_> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let glob = self.glob;
+        let layout = self.entries.layout;
+        self.entries
+            .find(|entry| glob.is_match(&layout.unescape(entry)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.entries.size_hint().1)
+    }
+}
+
+impl DoubleEndedIterator for GlobMatches<'_, '_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let glob = self.glob;
+        let layout = self.entries.layout;
+        self.entries
+            .rfind(|entry| glob.is_match(&layout.unescape(entry)))
+    }
+}
+
+impl FusedIterator for GlobMatches<'_, '_> {}
+
+/// Error that can occur when parsing a [`Glob`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GlobError {
+    /// A `[` was not followed by a closing `]`.
+    UnclosedClass {
+        /// The byte offset of the opening `[`.
+        start: usize,
+    },
+    /// The pattern ended in a `\`, with nothing to escape.
+    DanglingEscape,
+}
+
+impl Error for GlobError {}
+
+impl Display for GlobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnclosedClass { start } => {
+                write!(f, "The character class opened at {start} is not closed.")
+            }
+            Self::DanglingEscape => write!(f, "The pattern ends in an escape."),
+        }
+    }
+}
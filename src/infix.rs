@@ -0,0 +1,209 @@
+//! Infix (substring) queries, through a suffix array over the haystack.
+
+use crate::{Bytewise, Match, PrefixOrder, SortOrder, SortedString};
+use std::{borrow::Cow, ops::Range};
+
+/// Size of a single serialized position (a [`u32`]), see [`InfixIndex::as_bytes()`].
+const POSITION_SIZE: usize = 4;
+
+/// A [suffix array](https://en.wikipedia.org/wiki/Suffix_array) over the haystack of a
+/// [`SortedString`], answering which entries contain a needle anywhere.
+///
+/// Without it, infix queries require a linear scan of all entries. With it, they cost
+/// two binary searches, plus time proportional to the number of hits. The index holds
+/// one position per [`char`] of the haystack (separators excluded), in order of the
+/// text following each position up to the end of its entry. For escaped entries (see
+/// [`Format::with_escape()`](crate::Format::with_escape)), that text is unescaped, and
+/// there is one position per unescaped [`char`]. It therefore takes up
+/// about four times the haystack's size in bytes, and is best built ahead of time.
+///
+/// Positions are stored as little-endian [`u32`]s, so haystacks are limited to 4 GiB.
+/// The bytes can be written out using [`InfixIndex::as_bytes()`], e.g. from a build
+/// script, and loaded without copying using [`InfixIndex::from_bytes()`], e.g. from
+/// [`include_bytes!`]. An index is only meaningful for the haystack it was built from.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, InfixIndex, SortedString};
+///
+/// let ss = SortedString::new_checked("Apfel,Kapelle,Pfeil,Zaun", AsciiChar::Comma)?;
+///
+/// let bytes = InfixIndex::new(&ss).as_bytes().to_vec();
+/// // ... written out, and later read back in:
+/// let index = InfixIndex::from_bytes(&bytes).expect("valid index");
+///
+/// let entries = |needle| -> Vec<&str> {
+///     ss.entries_containing(&index, needle).iter().map(|m| m.entry).collect()
+/// };
+/// assert_eq!(entries("pfe"), vec!["Apfel"]);
+/// assert_eq!(entries("el"), vec!["Apfel", "Kapelle"]);
+/// assert_eq!(entries("xyz"), Vec::<&str>::new());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InfixIndex<'i> {
+    positions: Cow<'i, [u8]>,
+}
+
+impl InfixIndex<'static> {
+    /// Builds the index over the haystack of `ss`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the haystack is larger than [`u32::MAX`] bytes.
+    #[must_use]
+    pub fn new<O>(ss: &SortedString<'_, O>) -> Self
+    where
+        O: SortOrder,
+    {
+        let layout = ss.layout();
+        let mut suffixes = Vec::new();
+
+        for (range, entry) in ss.iter_with_ranges() {
+            suffixes.extend(
+                layout
+                    .format
+                    .char_offsets(entry, layout.sep.as_str())
+                    .into_iter()
+                    .map(|i| (range.start + i, ss.unescape(&entry[i..]))),
+            );
+        }
+
+        suffixes.sort_unstable_by(|(a_pos, a), (b_pos, b)| a.cmp(b).then(a_pos.cmp(b_pos)));
+
+        let positions = suffixes
+            .into_iter()
+            .flat_map(|(pos, _)| {
+                u32::try_from(pos)
+                    .expect("haystack exceeds 4 GiB")
+                    .to_le_bytes()
+            })
+            .collect();
+
+        Self {
+            positions: Cow::Owned(positions),
+        }
+    }
+}
+
+impl<'i> InfixIndex<'i> {
+    /// Loads an index previously serialized using [`InfixIndex::as_bytes()`], without
+    /// copying.
+    ///
+    /// Returns [`None`] if `bytes` cannot be an index, i.e. their number is not a
+    /// multiple of four. Whether the index matches the haystack it is used with cannot
+    /// be checked; mismatches produce wrong results, but never panic.
+    #[must_use]
+    pub fn from_bytes(bytes: &'i [u8]) -> Option<Self> {
+        (bytes.len() % POSITION_SIZE == 0).then_some(Self {
+            positions: Cow::Borrowed(bytes),
+        })
+    }
+
+    /// Returns the serialized index, see [`InfixIndex::from_bytes()`].
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.positions
+    }
+
+    /// Returns the number of positions in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len() / POSITION_SIZE
+    }
+
+    /// Returns whether the index holds no positions, i.e. the haystack is made of
+    /// empty entries only.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn position(&self, i: usize) -> usize {
+        let mut bytes = [0; POSITION_SIZE];
+        bytes.copy_from_slice(&self.positions[i * POSITION_SIZE..(i + 1) * POSITION_SIZE]);
+        u32::from_le_bytes(bytes) as usize
+    }
+
+    /// Returns the first index of the suffix array for which `pred` is `false`.
+    fn partition_point<O>(
+        &self,
+        ss: &SortedString<'_, O>,
+        mut pred: impl FnMut(&str) -> bool,
+    ) -> usize
+    where
+        O: SortOrder,
+    {
+        let mut low = 0;
+        let mut high = self.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if pred(&suffix_at(ss, self.position(mid))) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        low
+    }
+
+    /// Returns the locations of all entries containing `needle`, in haystack order.
+    pub(crate) fn entries_containing<'a, O>(
+        &self,
+        ss: &SortedString<'a, O>,
+        needle: &str,
+    ) -> Vec<Match<'a>>
+    where
+        O: SortOrder,
+    {
+        if needle.is_empty() {
+            return ss
+                .iter_with_ranges()
+                .map(|(range, entry)| Match { range, entry })
+                .collect();
+        }
+
+        let is_before = |suffix: &str| Bytewise.compare_prefix(suffix, needle).is_lt();
+        let is_within = |suffix: &str| Bytewise.compare_prefix(suffix, needle).is_le();
+
+        let front = self.partition_point(ss, is_before);
+        let back = self.partition_point(ss, is_within);
+
+        let mut ranges: Vec<Range<usize>> = (front..back.max(front))
+            .map(|i| self.position(i))
+            .filter(|pos| *pos < ss.string.len())
+            .map(|pos| ss.tile_around(pos).entry)
+            .collect();
+
+        ranges.sort_unstable_by_key(|range| range.start);
+        ranges.dedup();
+
+        ranges
+            .into_iter()
+            .map(|range| Match {
+                entry: &ss.string[range.clone()],
+                range,
+            })
+            .collect()
+    }
+}
+
+/// Returns the unescaped text from `pos` up to the end of its entry, or nothing if
+/// `pos` is invalid.
+fn suffix_at<'a, O>(ss: &SortedString<'a, O>, pos: usize) -> Cow<'a, str>
+where
+    O: SortOrder,
+{
+    if !ss.string.is_char_boundary(pos) {
+        return Cow::Borrowed("");
+    }
+
+    let end = ss.tile_around(pos).entry.end;
+    ss.unescape(ss.string.get(pos..end).unwrap_or_default())
+}
@@ -11,17 +11,58 @@
 #![allow(clippy::multiple_crate_versions)]
 #![doc = include_str!("../README.md")]
 
+mod bytes;
+#[cfg(feature = "unicode")]
+mod collation;
+mod format;
+mod fuzzy;
+mod glob;
+mod infix;
+#[cfg(feature = "unicode")]
+mod normalization;
+mod order;
+#[cfg(feature = "regex")]
+mod regex_query;
+mod separator;
+mod suffix;
+
 #[doc(no_inline)] // https://users.rust-lang.org/t/re-exporting-type-and-rustdoc/50847
 pub use ascii::AsciiChar;
+pub use bytes::SortedBytes;
+#[cfg(feature = "unicode")]
+pub use collation::Collation;
+pub use format::Format;
+pub use fuzzy::{EditDistance, FuzzyMatch};
+pub use glob::{Glob, GlobError, GlobMatches};
+pub use infix::InfixIndex;
 use itertools::Itertools;
-use std::{cmp::Ordering, error::Error, fmt::Display, ops::Range};
+#[cfg(feature = "unicode")]
+pub use normalization::NormalizationForm;
+#[cfg(feature = "unicode")]
+pub use order::DiacriticInsensitive;
+pub use order::{
+    Bytewise, CaseInsensitive, LengthFirst, Natural, PrefixOrder, Retrograde, Reversed, SortOrder,
+};
+#[cfg(feature = "regex")]
+pub use regex_query::{RegexMatches, RegexQuery};
+pub use separator::Separator;
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    error::Error,
+    fmt::Display,
+    iter::FusedIterator,
+    ops::{Bound, Range, RangeBounds},
+    str::Utf8Error,
+};
+pub use suffix::SuffixSortedString;
 
 /// Main type to perform binary search through.
 ///
 /// This type upholds the important invariants of [`SortedString::binary_search()`]:
 ///
 /// - a *sorted* string is required,
-/// - as well as some [`AsciiChar`] separator to split by.
+/// - as well as some [`Separator`] to split by.
 ///
 /// Access to binary search is gated behind this type. For this to not be too painful,
 /// the type is designed to be cheap, as it doesn't own the potentially large haystack
@@ -37,88 +78,150 @@ use std::{cmp::Ordering, error::Error, fmt::Display, ops::Range};
 /// 1. [`SortedString::new_checked()`]: recommended, ensuring soundness
 /// 2. [`SortedString::new_unchecked()`]: unsafe, faster
 ///
-/// ## Note on ASCII requirement
+/// # Ordering
 ///
-/// On instance creation, the separator has to be [within the ASCII
-/// range](https://doc.rust-lang.org/std/primitive.char.html#method.is_ascii). In other
-/// words, **multi-byte (in the sense of UTF-8) separators are not supported**. This
-/// leaves common separators like `\n`, `\t`, `-`, `,` and [many more][ascii::AsciiChar]
-/// available, while arbitrary [`char`]s are not allowed:
+/// By default, the haystack is expected to be sorted [`Bytewise`], as produced by
+/// [`SortedString::sort()`]. Other orderings are supported through the [`SortOrder`]
+/// type parameter, see the `*_with_order` constructors. The ordering is part of the
+/// type, so validation, sorting and searching cannot disagree. Queries which only make
+/// sense for some orderings are only available for those:
 ///
 /// ```compile_fail
-/// let sep = '🦀';
-/// let haystack = "a,b,c";
-/// // Not allowed:
-/// let _ = b4s::SortedString::new_checked(haystack, sep);
-/// ```
+/// use b4s::{AsciiChar, LengthFirst, SortedString};
 ///
-/// UTF-8 being a variable-length encoding, allowing *any* separator [`char`] would
-/// require an initial [linear scan of the input
-/// string](https://doc.rust-lang.org/std/primitive.str.html#method.char_indices):
-///
-/// - either at instance creation time, then saving the results,
-/// - or before each search.
+/// let sep = AsciiChar::Comma;
+/// let ss = SortedString::new_unchecked_with_order("b,ab", sep, LengthFirst);
+/// // Entries sharing a prefix are not adjacent under `LengthFirst`:
+/// let _ = ss.entries_with_prefix("a");
+/// ```
 ///
-/// The former takes up a lot of space ([`usize`] pointers, which on 64-bit are much
-/// larger than the separator itself), the latter time. Both are undesirable. Even more
-/// undesirable would be handling UTF-8 parsing and validation manually. The ASCII
-/// requirement [keeps things simple](https://en.wikipedia.org/wiki/KISS_principle) and
-/// is unlikely to be an impediment in real-world use.
+/// ## Separators
 ///
-/// As UTF-8 is fully ASCII-compatible, allowing only that means the string can be
-/// efficiently scanned as bytes. The needle and haystack can still be any UTF-8 string.
-/// No multi-byte code point (with its continuation bytes) [contains bytes in the ASCII
-/// range](https://en.wikipedia.org/wiki/UTF-8#Encoding). Scanning for such an ASCII
-/// separator is therefore sound:
+/// Entries are usually separated by a single [`AsciiChar`], such as `\n`, `\t`, `-`,
+/// `,` and [many more][ascii::AsciiChar]. As UTF-8 is fully ASCII-compatible, the
+/// string can then be efficiently scanned as bytes. The needle and haystack can still
+/// be any UTF-8 string. No multi-byte code point (with its continuation bytes)
+/// [contains bytes in the ASCII range](https://en.wikipedia.org/wiki/UTF-8#Encoding).
+/// Scanning for such an ASCII separator is therefore sound:
 ///
 /// - it won't be found at [non-char
 ///   boundaries](https://doc.rust-lang.org/std/primitive.str.html#method.is_char_boundary),
 /// - and it itself will be a boundary.
 ///
-/// These properties simplify scanning and allow it to be fast at both instance creation
-/// and search time. The downside is hopefully negligible and in the common case
-/// unnoticeable. Please contact the author if your use case requires multi-byte
-/// support.
+/// Longer separators, such as `\r\n` or `·`, are supported through [`Separator`]. The
+/// same properties hold for them, as UTF-8 is self-synchronizing. Mere [`char`]s are
+/// not accepted, to not mistake them for a [`str`] separator:
 ///
-/// The ASCII requirement is manifested in the [type
-/// system](https://dusted.codes/the-type-system-is-a-programmers-best-friend),
-/// enforcing correct usage [at compile time](https://cliffle.com/blog/rust-typestate/).
+/// ```compile_fail
+/// let sep = '🦀';
+/// let haystack = "a,b,c";
+/// // Not allowed:
+/// let _ = b4s::SortedString::new_checked(haystack, sep);
+/// ```
+///
+/// Entries are split the way [`str::split()`] does, matching separators from the left.
+/// Separators which can overlap themselves, such as `||`, make haystacks like `a|||b`
+/// ambiguous nonetheless: is the second entry `|b`, or the first one `a|`? Such
+/// haystacks are rejected by [`SortedString::new_checked()`]. Overlaps not affecting
+/// entries are fine, e.g. `a||||b` holds an empty entry between `a` and `b`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct SortedString<'a> {
+pub struct SortedString<'a, O = Bytewise> {
     string: &'a str,
-    sep: AsciiChar,
+    sep: Separator<'a>,
+    format: Format,
+    order: O,
 }
 
-impl Display for SortedString<'_> {
+impl<O> Display for SortedString<'_, O> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "SortedString({:?}, {:?})", self.string, self.sep)
+        // Single-byte separators are shown as their `AsciiChar`, e.g. `','`.
+        match self.sep.as_ascii() {
+            Some(sep) => write!(f, "SortedString({:?}, {:?})", self.string, sep),
+            None => write!(
+                f,
+                "SortedString({:?}, {:?})",
+                self.string,
+                self.sep.as_str()
+            ),
+        }
     }
 }
 
 /// Error returned in case of a failed search.
 ///
-/// [Thin wrapper](https://doc.rust-lang.org/rust-by-example/generics/new_types.html)
-/// for [`Range<usize>`], required to implement [`Error`], achieving a [useful
+/// Besides the location of the last comparison, it reports where the needle would have
+/// to be inserted to keep the haystack sorted, much like the `Err` variant of
+/// [`binary_search` of the standard
+/// library](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search).
+/// It implements [`Error`], achieving a [useful
 /// API](https://rust-lang.github.io/api-guidelines/interoperability.html#error-types-are-meaningful-and-well-behaved-c-good-err).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct SearchError(
+pub struct SearchError {
     /// Location of the last *unsuccessful* comparison.
     ///
-    /// This is *not* the location where the needle could be inserted. That location is
-    /// either to the left *or* right of this location, depending on how
-    /// [comparison](https://doc.rust-lang.org/std/primitive.str.html#impl-PartialOrd%3Cstr%3E-for-str)
-    /// goes. As this error is not particularly actionable at runtime, the exact
-    /// location (left, right) is not reported, saving computation at runtime and
-    /// affording a simpler implementation.
-    pub Range<usize>,
-);
+    /// The needle would be inserted directly next to this entry, on the given
+    /// [`side`](SearchError::side): it is the first entry comparing greater than the
+    /// needle for [`Side::Left`], or the last one comparing less for [`Side::Right`].
+    /// Which other entries are compared on the way there is not specified.
+    pub last_probe: Range<usize>,
+    /// Byte offset at which the needle would be inserted to keep the haystack sorted.
+    ///
+    /// This is the start of the first entry comparing greater than the needle, or the
+    /// haystack's length if there is no such entry. In the former case, insert the
+    /// needle followed by a separator, in the latter a separator followed by the
+    /// needle. If the haystack ends in a separator ignored by its [`Format`], the
+    /// needle is followed by a separator in either case.
+    pub insertion_point: usize,
+    /// Side of [`last_probe`](SearchError::last_probe) the needle would be inserted at.
+    pub side: Side,
+}
 
 impl Error for SearchError {}
 
 impl Display for SearchError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let SearchError(range) = self;
-        write!(f, "needle not found, last looked at {range:?}")
+        write!(
+            f,
+            "needle not found, last looked at {:?}, would be inserted at {} ({:?} of it)",
+            self.last_probe, self.insertion_point, self.side
+        )
+    }
+}
+
+/// Side of an entry a needle would be inserted at, see [`SearchError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Side {
+    /// The needle sorts before the entry.
+    Left,
+    /// The needle sorts after the entry.
+    Right,
+}
+
+/// A successful match, as returned by [`SortedString::search()`].
+///
+/// The entry is tied to the lifetime of the haystack, not the [`SortedString`], so it
+/// can outlive the latter.
+///
+/// The entry's *index* (its ordinal position among all entries) is not reported. As
+/// entries are unevenly sized, it is not known to binary search, and determining it
+/// would require a linear scan of the haystack up to the match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Match<'a> {
+    /// Location of the entry in the haystack, in bytes.
+    pub range: Range<usize>,
+    /// The matched entry itself.
+    pub entry: &'a str,
+}
+
+impl AsRef<str> for Match<'_> {
+    fn as_ref(&self) -> &str {
+        self.entry
+    }
+}
+
+impl Display for Match<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.entry)
     }
 }
 
@@ -170,7 +273,8 @@ impl<'a> SortedString<'a> {
     ///
     /// This error might also creep in when passing a newline-separated file containing
     /// a trailing newline. The empty string will be in last position, contradicting
-    /// sorting.
+    /// sorting. Use [`Format::with_trailing_separator()`] to ignore it, see
+    /// [`SortedString::new_checked_with_format()`].
     ///
     /// ```
     /// let sep = b4s::AsciiChar::LineFeed;
@@ -191,15 +295,272 @@ impl<'a> SortedString<'a> {
     ///
     /// assert_eq!(sorted_string, Err(b4s::SortedStringCreationError::EmptyHaystack));
     /// ```
-    pub fn new_checked(
+    pub fn new_checked<S>(haystack: &'a str, sep: S) -> Result<Self, SortedStringCreationError>
+    where
+        S: Into<Separator<'a>>,
+    {
+        Self::new_checked_with_order(haystack, sep, Bytewise)
+    }
+
+    /// Creates an instance of [`SortedString`] [without performing sanity
+    /// checks](https://rust-lang.github.io/api-guidelines/dependability.html#dynamic-enforcement-with-opt-out).
+    ///
+    /// This is essentially what conventionally would be a simple
+    /// [`new()`](https://rust-lang.github.io/api-guidelines/interoperability.html#types-eagerly-implement-common-traits-c-common-traits),
+    /// but specifically named to alert users to the dangers.
+    ///
+    /// # Example: Simple Use
+    ///
+    /// ```
+    /// let sep = b4s::AsciiChar::Comma;
+    /// let haystack = "a,b,c";
+    /// let sorted_string = b4s::SortedString::new_unchecked(haystack, sep);
+    /// ```
+    ///
+    /// # Example: Incorrect Use
+    ///
+    /// ```
+    /// use std::ops::Range;
+    ///
+    /// let sep = b4s::AsciiChar::Comma;
+    /// let unsorted_haystack = "a,c,b";
+    /// let sorted_string = b4s::SortedString::new_unchecked(unsorted_haystack, sep);
+    ///
+    /// // Unable to find element in unsorted haystack
+    /// assert_eq!(
+    ///     sorted_string.binary_search("b"),
+    ///     Err(b4s::SearchError {
+    ///         last_probe: Range { start: 0, end: 1 },
+    ///         insertion_point: 2,
+    ///         side: b4s::Side::Right,
+    ///     })
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn new_unchecked(string: &'a str, sep: AsciiChar) -> Self {
+        Self::new_unchecked_with_order(string, sep, Bytewise)
+    }
+
+    /// Convenience method to sort a [`str`] by a given separator, returning an owned
+    /// version.
+    ///
+    /// As [`SortedString`] is designed to be thin and doesn't own its data (expect for
+    /// the `sep`), this convenience method helps creating a sorted [`String`] in the
+    /// required format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// // Perhaps read directly from a file, where sorting is unreliable/unknown.
+    /// let unsorted_haystack = "c\nb\na";
+    /// let sorted_haystack = b4s::SortedString::sort(unsorted_haystack, sep);
+    ///
+    /// // Passes fine
+    /// let sorted_string = b4s::SortedString::new_checked(&sorted_haystack, sep)?;
+    ///
+    /// assert_eq!(
+    ///     sorted_string.binary_search("c"),
+    ///     Ok(std::ops::Range { start: 4, end: 5 })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn sort<'s, S>(string: &str, sep: S) -> String
+    where
+        S: Into<Separator<'s>>,
+    {
+        Self::sort_with_order(string, sep, &Bytewise)
+    }
+
+    /// Converts [`SortedBytes`] into a [`SortedString`], if they are valid UTF-8.
+    ///
+    /// Mirrors [`std::str::from_utf8()`]. Both are sorted bytewise, so no check for
+    /// sorting is needed. The separator has to be valid UTF-8 on its own, i.e. ASCII.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{SortedBytes, SortedString};
+    ///
+    /// let sb = SortedBytes::new_checked("Apfel,Äpfel".as_bytes(), b',')?;
+    /// let ss = SortedString::from_utf8(sb)?;
+    /// assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["Apfel", "Äpfel"]);
+    ///
+    /// // "Äpfel" in Latin-1
+    /// let sb = SortedBytes::new_checked(b"Apfel,\xC4pfel", b',')?;
+    /// assert!(SortedString::from_utf8(sb).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Utf8Error`] if the haystack or the separator are not valid UTF-8.
+    pub fn from_utf8(bytes: SortedBytes<'a>) -> Result<Self, Utf8Error> {
+        let string = std::str::from_utf8(bytes.as_bytes())?;
+        let sep = Separator::from_utf8_byte(bytes.separator())?;
+
+        Ok(Self::new(string, sep, Format::new(), Bytewise))
+    }
+
+    /// Returns all entries within `max_distance` edits of `needle`, in sorted order.
+    ///
+    /// Distances count [`char`]s, not bytes, and are measured according to `metric`.
+    /// As the haystack is sorted, entries sharing a prefix are adjacent: the distances
+    /// computed for a prefix are reused for all entries sharing it, and once a prefix
+    /// exceeds `max_distance` on its own, all entries starting with it are skipped
+    /// using binary search. Only entries close to the needle are therefore visited,
+    /// similar to a [Levenshtein
+    /// automaton](https://en.wikipedia.org/wiki/Levenshtein_automaton) run over a
+    /// trie.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::EditDistance;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Angel\nApfel\nAptel\nBaum\nPfeil";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// let matches = ss.fuzzy_search("Apefl", 2, EditDistance::Levenshtein);
+    /// let found: Vec<_> = matches.iter().map(|m| (m.entry, m.distance)).collect();
+    /// assert_eq!(found, vec![("Apfel", 2), ("Aptel", 2)]);
+    ///
+    /// let matches = ss.fuzzy_search("Apefl", 2, EditDistance::Damerau);
+    /// let found: Vec<_> = matches.iter().map(|m| (m.entry, m.distance)).collect();
+    /// assert_eq!(found, vec![("Apfel", 1), ("Aptel", 2)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn fuzzy_search<U>(
+        &self,
+        needle: U,
+        max_distance: usize,
+        metric: EditDistance,
+    ) -> Vec<FuzzyMatch<'a>>
+    where
+        U: AsRef<str>,
+    {
+        let mut rows = fuzzy::Rows::new(needle.as_ref(), max_distance, metric);
+        let mut matches = Vec::new();
+
+        let tiled_len = self.tiled_len();
+        let mut pos = 0;
+        while pos < tiled_len {
+            let Tile {
+                entry: range,
+                tiles,
+            } = self.tile_around(pos);
+            let entry = &self.string[range.clone()];
+            pos = tiles.end;
+
+            let unescaped = self.unescape(entry);
+
+            match rows.feed(&unescaped) {
+                fuzzy::Feed::Within(distance) => matches.push(FuzzyMatch {
+                    range,
+                    entry,
+                    distance,
+                }),
+                fuzzy::Feed::Beyond => {}
+                fuzzy::Feed::Dead { prefix_len } => {
+                    let dead = &unescaped[..prefix_len];
+                    // Never move backwards, even on unsorted, unchecked haystacks.
+                    pos = pos.max(self.partition_point_tiled(|entry| {
+                        Bytewise.compare_prefix(&self.unescape(entry), dead).is_le()
+                    }));
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+impl<'a, O> SortedString<'a, O>
+where
+    O: SortOrder,
+{
+    /// Creates a new instance of [`SortedString`] sorted by the given `order`,
+    /// performing sanity checks.
+    ///
+    /// Works like [`SortedString::new_checked()`], which is this method for the default
+    /// [`Bytewise`] order. Checking whether the haystack is sorted uses `order`, as do
+    /// all searches on the created instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use b4s::{AsciiChar, LengthFirst, SortedString, SortedStringCreationError};
+    ///
+    /// let haystack = "c,ab,abc";
+    /// let sep = AsciiChar::Comma;
+    /// assert!(
+    ///     SortedString::new_checked_with_order(haystack, sep, LengthFirst).is_ok()
+    /// );
+    /// assert_eq!(
+    ///     SortedString::new_checked(haystack, sep),
+    ///     Err(SortedStringCreationError::NotSorted)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SortedString::new_checked()`].
+    pub fn new_checked_with_order<S>(
         haystack: &'a str,
-        sep: AsciiChar,
-    ) -> Result<Self, SortedStringCreationError> {
-        if haystack.is_empty() {
+        sep: S,
+        order: O,
+    ) -> Result<Self, SortedStringCreationError>
+    where
+        S: Into<Separator<'a>>,
+    {
+        Self::new_checked_with_format(haystack, sep, order, Format::new())
+    }
+
+    /// Creates a new instance of [`SortedString`] whose haystack is laid out according
+    /// to `format`, performing sanity checks.
+    ///
+    /// Works like [`SortedString::new_checked_with_order()`], which is this method for
+    /// the default [`Format`]. Entries ignored by `format` are not validated, and if
+    /// there are no others, creation fails with
+    /// [`SortedStringCreationError::EmptyHaystack`]. See [`Format`] for examples.
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SortedString::new_checked()`]. Additionally, fails with
+    /// [`SortedStringCreationError::EscapeInSeparator`] if the escape byte of `format`
+    /// (see [`Format::with_escape()`]) is part of `sep`.
+    pub fn new_checked_with_format<S>(
+        haystack: &'a str,
+        sep: S,
+        order: O,
+        format: Format,
+    ) -> Result<Self, SortedStringCreationError>
+    where
+        S: Into<Separator<'a>>,
+    {
+        let sep = sep.into();
+        if format.escapes_within(sep.as_str()) {
+            return Err(SortedStringCreationError::EscapeInSeparator);
+        }
+
+        let sorted_string = Self::new(haystack, sep, format, order);
+
+        if haystack.is_empty() || sorted_string.tiled_len() == 0 {
             return Err(SortedStringCreationError::EmptyHaystack);
         }
 
-        let sorted_string = Self::new(haystack, sep);
+        if !sep.splits_unambiguously(haystack, format.escape_byte()) {
+            return Err(SortedStringCreationError::AmbiguousSeparator);
+        }
 
         if sorted_string.is_sorted() {
             Ok(sorted_string)
@@ -208,6 +569,165 @@ impl<'a> SortedString<'a> {
         }
     }
 
+    /// Creates an instance of [`SortedString`] sorted by the given `order`, without
+    /// performing sanity checks.
+    ///
+    /// Works like [`SortedString::new_unchecked()`], which is this method for the
+    /// default [`Bytewise`] order. Usable in `const` contexts as well:
+    ///
+    /// ```
+    /// use b4s::{AsciiChar, Bytewise, Reversed, SortedString};
+    ///
+    /// const SEP: AsciiChar = AsciiChar::Comma;
+    /// static SS: SortedString<Reversed> =
+    ///     SortedString::new_unchecked_with_order("c,b,a", SEP, Reversed(Bytewise));
+    ///
+    /// assert!(SS.binary_search("b").is_ok());
+    /// ```
+    #[must_use]
+    pub const fn new_unchecked_with_order(string: &'a str, sep: AsciiChar, order: O) -> Self {
+        Self::new(string, Separator::from_ascii(sep), Format::new(), order)
+    }
+
+    /// Creates an instance of [`SortedString`] with a [`Separator`] of any length,
+    /// without performing sanity checks.
+    ///
+    /// Works like [`SortedString::new_unchecked_with_order()`], which is this method
+    /// for [`AsciiChar`] separators:
+    ///
+    /// ```
+    /// use b4s::{Bytewise, Separator, SortedString};
+    ///
+    /// static SS: SortedString = SortedString::new_unchecked_with_separator(
+    ///     "a\r\nb",
+    ///     Separator::CRLF,
+    ///     Bytewise,
+    /// );
+    ///
+    /// assert_eq!(SS.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    /// ```
+    #[must_use]
+    pub const fn new_unchecked_with_separator(
+        string: &'a str,
+        sep: Separator<'a>,
+        order: O,
+    ) -> Self {
+        Self::new(string, sep, Format::new(), order)
+    }
+
+    /// Creates an instance of [`SortedString`] whose haystack is laid out according to
+    /// `format`, without performing sanity checks.
+    ///
+    /// Works like [`SortedString::new_unchecked_with_separator()`], which is this
+    /// method for the default [`Format`]. Useful for files included at compile time,
+    /// which usually end in a line feed:
+    ///
+    /// ```
+    /// use b4s::{AsciiChar, Bytewise, Format, Separator, SortedString};
+    ///
+    /// static SS: SortedString = SortedString::new_unchecked_with_format(
+    ///     "Aachen\nAmpel\nApfel\n", // e.g. `include_str!("words.txt")`
+    ///     Separator::from_ascii(AsciiChar::LineFeed),
+    ///     Bytewise,
+    ///     Format::new().with_trailing_separator(),
+    /// );
+    ///
+    /// assert_eq!(SS.iter().next_back(), Some("Apfel"));
+    /// assert!(SS.binary_search("").is_err());
+    /// ```
+    #[must_use]
+    pub const fn new_unchecked_with_format(
+        string: &'a str,
+        sep: Separator<'a>,
+        order: O,
+        format: Format,
+    ) -> Self {
+        Self::new(string, sep, format, order)
+    }
+
+    /// Convenience method to sort a [`str`] by a given separator and `order`, returning
+    /// an owned version.
+    ///
+    /// Works like [`SortedString::sort()`], which is this method for the default
+    /// [`Bytewise`] order. Entries comparing equal keep their relative order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, LengthFirst, SortedString};
+    ///
+    /// let sep = AsciiChar::Comma;
+    /// let sorted_haystack =
+    ///     SortedString::sort_with_order("abc,c,ab", sep, &LengthFirst);
+    /// assert_eq!(sorted_haystack, "c,ab,abc");
+    ///
+    /// let sorted_string =
+    ///     SortedString::new_checked_with_order(&sorted_haystack, sep, LengthFirst)?;
+    /// assert!(sorted_string.binary_search("ab").is_ok());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn sort_with_order<'s, S>(string: &str, sep: S, order: &O) -> String
+    where
+        S: Into<Separator<'s>>,
+    {
+        Self::sort_with_format(string, sep, order, Format::new())
+    }
+
+    /// Convenience method to sort a [`str`] laid out according to `format`, returning
+    /// an owned version.
+    ///
+    /// Works like [`SortedString::sort_with_order()`], which is this method for the
+    /// default [`Format`]. Entries ignored by `format` are dropped. If `format` allows
+    /// for a trailing separator, the result ends in one, as text files conventionally
+    /// do. Entries ending in a lone escape (see [`Format::with_escape()`]) get it
+    /// escaped, so it does not escape the separator following it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use b4s::{AsciiChar, Bytewise, Format, SortedString};
+    ///
+    /// let format = Format::new().with_trailing_separator().skipping_empty_entries();
+    /// let sep = AsciiChar::LineFeed;
+    /// let sorted = SortedString::sort_with_format("b\n\na\n", sep, &Bytewise, format);
+    /// assert_eq!(sorted, "a\nb\n");
+    /// ```
+    #[must_use]
+    pub fn sort_with_format<'s, S>(string: &str, sep: S, order: &O, format: Format) -> String
+    where
+        S: Into<Separator<'s>>,
+    {
+        let layout = Layout {
+            string,
+            sep: sep.into(),
+            format,
+        };
+        let entries = layout.entries();
+        let is_empty = entries.span().is_none();
+
+        let sorted = entries
+            .sorted_by(|a, b| order.compare(&layout.unescape(a), &layout.unescape(b)))
+            .collect::<Vec<&str>>();
+
+        let mut joined = String::with_capacity(string.len() + sorted.len());
+        for (i, entry) in sorted.iter().enumerate() {
+            joined.push_str(entry);
+
+            if i + 1 < sorted.len() || (format.has_trailing_separator() && !is_empty) {
+                // A lone escape at the end would escape the following separator.
+                if let Some(escape) = format.dangling_escape(entry) {
+                    joined.push(escape);
+                }
+                joined.push_str(layout.sep.as_str());
+            }
+        }
+
+        joined
+    }
+
     /// Searches for a needle inside this [`SortedString`].
     ///
     /// The return type aims to imitate [`binary_search` of the standard
@@ -245,7 +765,8 @@ impl<'a> SortedString<'a> {
     ///
     /// ## Needle contains separator
     ///
-    /// This situation not handled specially. Such needles will be impossible to find:
+    /// For the default [`Format`], this situation is not handled specially. Such
+    /// needles will be impossible to find:
     ///
     /// ```
     /// use std::{error::Error, ops::Range};
@@ -257,7 +778,7 @@ impl<'a> SortedString<'a> {
     ///
     ///     let needle = "Angel";
     ///     let result = ss.binary_search(needle);
-    ///     assert_eq!(result, Err(b4s::SearchError(Range { start: 0, end: 0 })));
+    ///     assert!(result.is_err());
     ///
     ///     let needle = "ngel\n";
     ///     let range = ss.binary_search(needle)?; // Works (note ? operator also works)
@@ -267,6 +788,45 @@ impl<'a> SortedString<'a> {
     /// }
     /// ```
     ///
+    /// Entries containing the separator can be found if it is escaped in the haystack,
+    /// see [`Format::with_escape()`]. Needles are not escaped:
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, Bytewise, Format, SortedString};
+    ///
+    /// let haystack = r"Aachen,Ampel\, rot,Apfel";
+    /// let format = Format::new().with_escape(AsciiChar::BackSlash);
+    /// let sep = AsciiChar::Comma;
+    /// let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format)?;
+    ///
+    /// let range = ss.binary_search("Ampel, rot")?;
+    /// assert_eq!(&haystack[range], r"Ampel\, rot");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Inserting a missing needle
+    ///
+    /// A failed search reports where the needle would have to go to keep the haystack
+    /// sorted, allowing an owned copy to be extended:
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let mut haystack = String::from("Aachen\nAmpel\nApfel");
+    /// let ss = b4s::SortedString::new_checked(&haystack, sep)?;
+    ///
+    /// let err = ss.binary_search("Angel").unwrap_err();
+    /// assert_eq!(err.insertion_point, 13);
+    /// assert_eq!(err.side, b4s::Side::Left);
+    ///
+    /// haystack.insert_str(err.insertion_point, "Angel\n");
+    /// assert_eq!(haystack, "Aachen\nAmpel\nAngel\nApfel");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
     /// # Errors
     ///
     /// Refer to [`SearchError`] for more info.
@@ -300,38 +860,40 @@ impl<'a> SortedString<'a> {
     ///
     /// - The haystack is `&str`, aka valid UTF-8. **Users cannot pass malformed
     ///   UTF-8.**
-    /// - The separator is ASCII, as ensured by signatures working off [`AsciiChar`],
-    ///   providing [type-level
+    /// - The separator is a [`Separator`], which is valid UTF-8 itself, as ensured by
+    ///   its constructors taking [`AsciiChar`] or `&str`, providing [type-level
     ///   guarantees](https://rust-lang.github.io/api-guidelines/dependability.html#static-enforcement).
-    ///   **Users can only pass single-byte UTF-8 values.**
-    /// - The separator being ASCII ([highest bit
-    ///   0](https://en.wikipedia.org/wiki/ASCII#8-bit_codes)), it cannot be part of any
-    ///   code point encoded as [multiple bytes using UTF-8 (highest bit
-    ///   1)](https://en.wikipedia.org/wiki/UTF-8#Encoding), as
-    ///   [validated](https://doc.rust-lang.org/std/primitive.char.html#validity) in
+    ///   Raw bytes are validated first. **Users cannot pass separators of malformed
+    ///   UTF-8**, such as single bytes with highest bit 1:
+    ///
+    ///   ```
+    ///   for byte in 0x80u8..=0xFFu8 {
+    ///       assert!(b4s::Separator::from_bytes(&[byte]).is_none());
+    ///   }
+    ///   ```
+    /// - UTF-8 is [self-synchronizing](https://en.wikipedia.org/wiki/UTF-8#Encoding):
+    ///   the first byte of an encoded code point is never a continuation byte (highest
+    ///   bits `10`), and all its other bytes are, as
+    ///   [validated](https://doc.rust-lang.org/std/primitive.char.html#validity) in
     ///   this sanity check:
     ///
     ///      ```
     ///      for code_point in 0..=0x10FFFF {
     ///          if let Some(c) = std::char::from_u32(code_point) {
-    ///              if c.is_ascii() {
-    ///                  continue;
-    ///              }
-    ///              for byte in c.to_string().bytes() {
-    ///                  assert!(!byte.is_ascii());
+    ///              let encoded = c.to_string();
+    ///              let (first, rest) = encoded.as_bytes().split_first().unwrap();
+    ///              assert_ne!(first & 0b1100_0000, 0b1000_0000);
+    ///              for byte in rest {
+    ///                  assert_eq!(byte & 0b1100_0000, 0b1000_0000);
     ///              }
     ///          }
     ///      }
     ///      ```
-    /// - *Single*-byte values with highest bit 1 are outside the ASCII range and
-    ///   **invalid UTF-8**:
-    ///
-    ///   ```
-    ///   for byte in 0x80u8..=0xFFu8 {
-    ///       assert!(!byte.is_ascii());
-    ///       assert!(std::str::from_utf8(&[byte]).is_err());
-    ///   }
-    ///   ```
+    /// - A separator found in the haystack therefore starts with the first byte of a
+    ///   code point, and ends with the last byte of one. It cannot be found inside of
+    ///   multi-byte code points, so it only ever occurs at [char
+    ///   boundaries](https://doc.rust-lang.org/std/primitive.str.html#method.is_char_boundary),
+    ///   where slicing the haystack succeeds.
     ///
     /// ### Fuzz Testing
     ///
@@ -385,146 +947,1414 @@ impl<'a> SortedString<'a> {
     where
         U: AsRef<str>,
     {
-        let haystack = self.string;
         let needle = needle.as_ref();
+        self.binary_search_by(|entry| self.order.compare(&self.unescape(entry), needle))
+    }
 
-        let leftmost = 0;
-        let rightmost = haystack.len();
-
-        let mut low = leftmost;
-        let mut high = rightmost;
-
-        let mut start = leftmost;
-        let mut end = rightmost;
-
-        let haystack = haystack.as_bytes();
-
-        let pred = |c: &&u8| **c == self.sep.as_byte();
+    /// Binary searches this [`SortedString`] with a comparator function.
+    ///
+    /// Mirrors [`binary_search_by` of the standard
+    /// library](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by):
+    /// the comparator is called with entries of the haystack and returns whether an
+    /// entry is [`Less`](Ordering::Less), [`Equal`](Ordering::Equal) or
+    /// [`Greater`](Ordering::Greater) than the desired target. The haystack has to be
+    /// sorted consistently with the comparator, otherwise results are meaningless (but
+    /// no panics occur).
+    ///
+    /// # Example
+    ///
+    /// Searching case-insensitively, in a haystack sorted accordingly:
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "apfel\nBaum\nzaun";
+    /// let ss = b4s::SortedString::new_unchecked(haystack, sep);
+    ///
+    /// let needle = "BAUM";
+    /// let result = ss.binary_search_by(|entry| {
+    ///     let entry = entry.chars().flat_map(char::to_lowercase);
+    ///     entry.cmp(needle.chars().flat_map(char::to_lowercase))
+    /// });
+    /// assert_eq!(result, Ok(std::ops::Range { start: 6, end: 10 }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SearchError`] for more info.
+    pub fn binary_search_by<F>(&self, cmp: F) -> SearchResult
+    where
+        F: FnMut(&'a str) -> Ordering,
+    {
+        let initial_probe = Range { start: 0, end: 0 };
+        self.bisect(cmp, 0, self.tiled_len(), initial_probe, Side::Left)
+    }
 
-        while low < high {
-            let mid = low + (high - low) / 2;
+    /// Binary searches this [`SortedString`] with a key extraction function.
+    ///
+    /// Mirrors [`binary_search_by_key` of the standard
+    /// library](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by_key):
+    /// the haystack has to be sorted by the key, for example a part of each entry.
+    /// See also [`SortedString::binary_search_by()`].
+    ///
+    /// # Example
+    ///
+    /// Searching by the part of each entry before a colon:
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::ops::Range;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "apple:red\nbanana:yellow\ncherry:red";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// fn key(entry: &str) -> &str {
+    ///     entry.split_once(':').map_or(entry, |(key, _)| key)
+    /// }
+    ///
+    /// let range = ss.binary_search_by_key(&"banana", key)?;
+    /// assert_eq!(&haystack[range], "banana:yellow");
+    ///
+    /// assert!(ss.binary_search_by_key(&"blueberry", key).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SearchError`] for more info.
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> SearchResult
+    where
+        F: FnMut(&'a str) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|entry| f(entry).cmp(b))
+    }
 
-            start = match haystack[..mid].iter().rev().find_position(pred) {
-                Some((delta, _)) => mid - delta,
-                None => leftmost,
-            };
+    /// Searches for many needles at once, returning one result per needle, in order.
+    ///
+    /// Results are the same as calling [`SortedString::binary_search()`] for each
+    /// needle, save for [`SearchError::last_probe`] (and [`SearchError::side`] relative
+    /// to it) and, in case of duplicate entries, which one of them is found. However,
+    /// if needles arrive in sorted order, the search for each needle starts off where
+    /// the previous one ended, *galloping* (exponentially widening the window) forward
+    /// from there instead of restarting from the full haystack. For many needles close
+    /// to one another, as is common when checking sorted tokens of a document, this
+    /// saves a lot of comparisons. Needles arriving out of order are supported, but do
+    /// not benefit from this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nAngel\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// let mut tokens = vec!["Apfel", "Birne", "Ampel", "Angel", "Ampel"];
+    /// tokens.sort_unstable();
+    ///
+    /// let results = ss.binary_search_many(&tokens);
+    /// let found = results.iter().filter(|result| result.is_ok()).count();
+    /// assert_eq!(found, 4);
+    /// assert_eq!(results[4].as_ref().unwrap_err().insertion_point, haystack.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn binary_search_many<I>(&self, needles: I) -> Vec<SearchResult>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let needles = needles.into_iter();
+        let mut results: Vec<SearchResult> = Vec::with_capacity(needles.size_hint().0);
+        let mut previous: Option<I::Item> = None;
 
-            end = match haystack[mid..].iter().find_position(pred) {
-                Some((delta, _)) => mid + delta,
-                None => rightmost,
+        for needle in needles {
+            let start = match (&previous, results.last()) {
+                (Some(previous), Some(result))
+                    if self
+                        .order
+                        .compare(previous.as_ref(), needle.as_ref())
+                        .is_le() =>
+                {
+                    match result {
+                        Ok(range) => range.start,
+                        Err(SearchError {
+                            last_probe,
+                            side: Side::Left,
+                            ..
+                        }) => last_probe.start,
+                        Err(SearchError {
+                            last_probe,
+                            side: Side::Right,
+                            ..
+                        }) => self.tile_around(last_probe.start).tiles.end,
+                    }
+                }
+                _ => 0,
             };
 
-            let haystack_word = std::str::from_utf8(&haystack[start..end])
-                .expect("Indices aren't valid for slicing into haystack. They are at ASCII chars and therefore always assumed valid.");
-
-            match needle.cmp(haystack_word) {
-                Ordering::Less => high = mid.saturating_sub(1),
-                Ordering::Equal => return Ok(Range { start, end }),
-                Ordering::Greater => low = mid + 1,
-            }
+            results.push(self.gallop(needle.as_ref(), start));
+            previous = Some(needle);
         }
 
-        Err(SearchError(Range { start, end }))
+        results
     }
 
-    /// Creates an instance of [`SortedString`] [without performing sanity
-    /// checks](https://rust-lang.github.io/api-guidelines/dependability.html#dynamic-enforcement-with-opt-out).
+    /// Searches for a needle inside this [`SortedString`], returning a [`Match`].
     ///
-    /// This is essentially what conventionally would be a simple
-    /// [`new()`](https://rust-lang.github.io/api-guidelines/interoperability.html#types-eagerly-implement-common-traits-c-common-traits),
-    /// but specifically named to alert users to the dangers.
+    /// Works exactly like [`SortedString::binary_search()`], but hands back the matched
+    /// entry as a slice of the haystack alongside its location, sparing callers from
+    /// slicing into the haystack themselves.
     ///
-    /// # Example: Simple Use
+    /// # Example
     ///
     /// ```
-    /// let sep = b4s::AsciiChar::Comma;
-    /// let haystack = "a,b,c";
-    /// let sorted_string = b4s::SortedString::new_unchecked(haystack, sep);
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// let m = ss.search("Ampel")?;
+    /// assert_eq!(m.entry, "Ampel");
+    /// assert_eq!(m.range, std::ops::Range { start: 7, end: 12 });
+    ///
+    /// assert!(ss.search("Angel").is_err());
+    /// # Ok(())
+    /// # }
     /// ```
     ///
-    /// # Example: Incorrect Use
+    /// # Errors
+    ///
+    /// Refer to [`SearchError`] for more info.
+    pub fn search<U>(&self, needle: U) -> Result<Match<'a>, SearchError>
+    where
+        U: AsRef<str>,
+    {
+        self.binary_search(needle).map(|range| Match {
+            entry: &self.string[range.clone()],
+            range,
+        })
+    }
+
+    /// Returns an iterator over all entries equal to `needle`, in sorted order.
+    ///
+    /// Under [`Bytewise`] ordering, equal entries are duplicates. Under looser
+    /// orderings such as [`CaseInsensitive`], several distinct entries may compare
+    /// equal to `needle`, while [`SortedString::search()`] only returns one of them.
+    /// Use [`Entries::span()`] to obtain the byte range spanned by all of them.
+    ///
+    /// # Example
     ///
     /// ```
-    /// use std::ops::Range;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, CaseInsensitive, SortedString};
     ///
-    /// let sep = b4s::AsciiChar::Comma;
-    /// let unsorted_haystack = "a,c,b";
-    /// let sorted_string = b4s::SortedString::new_unchecked(unsorted_haystack, sep);
+    /// let sep = AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nampel\nAmpel\nAMPEL\nApfel";
+    /// let ss = SortedString::new_checked_with_order(haystack, sep, CaseInsensitive)?;
     ///
-    /// // Unable to find element in unsorted haystack
-    /// assert_eq!(
-    ///     sorted_string.binary_search("b"),
-    ///     Err(b4s::SearchError(Range { start: 0, end: 1 }))
-    /// );
+    /// let entries = ss.search_all("Ampel");
+    /// assert_eq!(entries.span(), Some(std::ops::Range { start: 7, end: 24 }));
+    /// assert_eq!(entries.collect::<Vec<_>>(), vec!["ampel", "Ampel", "AMPEL"]);
+    ///
+    /// assert_eq!(ss.search_all("Angel").next(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_all<U>(&self, needle: U) -> Entries<'a>
+    where
+        U: AsRef<str>,
+    {
+        let needle = needle.as_ref();
+        self.range(needle..=needle)
+    }
+
+    /// Returns all entries containing `needle` anywhere, in sorted order.
+    ///
+    /// Uses a suffix array `index` built over this haystack, see [`InfixIndex`], so
+    /// that finding the entries costs two binary searches over it. Each entry is
+    /// returned once, regardless of how often it contains `needle`. An empty `needle`
+    /// is contained in all entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::InfixIndex;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Abendbrot\nBrot\nBrotbox\nRotkohl";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    /// let index = InfixIndex::new(&ss);
+    ///
+    /// let matches = ss.entries_containing(&index, "rot");
+    /// let entries: Vec<_> = matches.iter().map(|m| m.entry).collect();
+    /// assert_eq!(entries, vec!["Abendbrot", "Brot", "Brotbox"]);
+    /// assert_eq!(matches[1].range, std::ops::Range { start: 10, end: 14 });
+    /// # Ok(())
+    /// # }
     /// ```
     #[must_use]
-    pub const fn new_unchecked(string: &'a str, sep: AsciiChar) -> Self {
-        Self::new(string, sep)
+    pub fn entries_containing<U>(&self, index: &InfixIndex<'_>, needle: U) -> Vec<Match<'a>>
+    where
+        U: AsRef<str>,
+    {
+        index.entries_containing(self, needle.as_ref())
     }
 
-    /// Convenience method to sort a [`str`] by a given separator, returning an owned
-    /// version.
+    /// Returns an iterator over all entries, in sorted order.
     ///
-    /// As [`SortedString`] is designed to be thin and doesn't own its data (expect for
-    /// the `sep`), this convenience method helps creating a sorted [`String`] in the
-    /// required format.
+    /// The entries are slices of the haystack, so no copying occurs, and the separator
+    /// does not need to be known to callers. Iteration is possible from both ends. See
+    /// [`SortedString::iter_with_ranges()`] to also obtain the entries' locations.
     ///
     /// # Example
     ///
     /// ```
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let sep = b4s::AsciiChar::LineFeed;
-    /// // Perhaps this was read directly from a file, where sorting is unreliable/unknown.
-    /// let unsorted_haystack = "c\nb\na";
-    /// let sorted_haystack = b4s::SortedString::sort(unsorted_haystack, sep);
+    /// let haystack = "Aachen\nAmpel\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
     ///
-    /// // Passes fine
-    /// let sorted_string = b4s::SortedString::new_checked(&sorted_haystack, sep)?;
+    /// assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["Aachen", "Ampel", "Apfel"]);
+    /// assert_eq!(ss.iter().next_back(), Some("Apfel"));
+    ///
+    /// for entry in &ss {
+    ///     assert!(entry.starts_with('A'));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> Entries<'a> {
+        self.entries_between(0, self.tiled_len())
+    }
+
+    /// Returns an iterator over all entries alongside their locations, in sorted order.
+    ///
+    /// Shorthand for [`Entries::with_ranges()`] on [`SortedString::iter()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::ops::Range;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
     ///
     /// assert_eq!(
-    ///     sorted_string.binary_search("c"),
-    ///     Ok(std::ops::Range { start: 4, end: 5 })
+    ///     ss.iter_with_ranges().collect::<Vec<_>>(),
+    ///     vec![
+    ///         (Range { start: 0, end: 6 }, "Aachen"),
+    ///         (Range { start: 7, end: 12 }, "Ampel"),
+    ///         (Range { start: 13, end: 18 }, "Apfel"),
+    ///     ]
     /// );
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn sort(string: &str, sep: AsciiChar) -> String {
-        string
-            .split(sep.as_char())
-            .sorted()
-            .collect::<Vec<&str>>()
-            .join(&sep.to_string())
+    pub fn iter_with_ranges(&self) -> EntriesWithRanges<'a> {
+        self.iter().with_ranges()
     }
 
-    const fn new(string: &'a str, sep: AsciiChar) -> Self {
-        Self { string, sep }
+    /// Returns `entry`, as yielded by queries, in its unescaped form.
+    ///
+    /// Entries are returned as-is, without allocating, unless the haystack's [`Format`]
+    /// has an escape byte (see [`Format::with_escape()`]) and they contain it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, Bytewise, Format, SortedString};
+    ///
+    /// let format = Format::new().with_escape(AsciiChar::BackSlash);
+    /// let haystack = r"C:\\Temp,a\,b\\,c";
+    /// let sep = AsciiChar::Comma;
+    /// let ss =
+    ///     SortedString::new_checked_with_format(haystack, sep, Bytewise, format)?;
+    ///
+    /// let entries = ss.iter().map(|entry| ss.unescape(entry)).collect::<Vec<_>>();
+    /// assert_eq!(entries, vec![r"C:\Temp", r"a,b\", "c"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn unescape(&self, entry: &'a str) -> Cow<'a, str> {
+        self.layout().unescape(entry)
     }
 
-    fn is_sorted(&self) -> bool {
-        self.string
-            .split(self.sep.as_char())
-            .tuple_windows()
-            .all(|(a, b)| a <= b)
+    /// Returns an iterator over all entries within the given lexicographic bounds, in
+    /// sorted order.
+    ///
+    /// Works like [`BTreeSet::range`](std::collections::BTreeSet::range): bounds can be
+    /// inclusive, exclusive or open-ended. Use [`Entries::span()`] to obtain the byte
+    /// range spanned by the resulting entries. The iterator is double-ended, so it can
+    /// be [reversed](Iterator::rev) as well. Unlike its standard library counterpart,
+    /// this method does not panic if the start bound exceeds the end bound, but yields
+    /// no entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::ops::Bound;
+    ///
+    /// let sep = b4s::AsciiChar::Comma;
+    /// let haystack = "fob,foo,fooo,fop,fox";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// let entries = ss.range("foo".."fop");
+    /// assert_eq!(entries.span(), Some(std::ops::Range { start: 4, end: 12 }));
+    /// assert_eq!(entries.collect::<Vec<_>>(), vec!["foo", "fooo"]);
+    ///
+    /// let entries = ss.range("foo"..="fop").rev();
+    /// assert_eq!(entries.collect::<Vec<_>>(), vec!["fop", "fooo", "foo"]);
+    ///
+    /// let entries = ss.range::<str, _>((Bound::Excluded("fop"), Bound::Unbounded));
+    /// assert_eq!(entries.collect::<Vec<_>>(), vec!["fox"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn range<K, R>(&self, range: R) -> Entries<'a>
+    where
+        K: AsRef<str> + ?Sized,
+        R: RangeBounds<K>,
+    {
+        let front = match range.start_bound() {
+            Bound::Included(start) => {
+                let start = start.as_ref();
+                self.partition_point_tiled(|entry| {
+                    self.order.compare(&self.unescape(entry), start).is_lt()
+                })
+            }
+            Bound::Excluded(start) => {
+                let start = start.as_ref();
+                self.partition_point_tiled(|entry| {
+                    self.order.compare(&self.unescape(entry), start).is_le()
+                })
+            }
+            Bound::Unbounded => 0,
+        };
+
+        let back = match range.end_bound() {
+            Bound::Included(end) => {
+                let end = end.as_ref();
+                self.partition_point_tiled(|entry| {
+                    self.order.compare(&self.unescape(entry), end).is_le()
+                })
+            }
+            Bound::Excluded(end) => {
+                let end = end.as_ref();
+                self.partition_point_tiled(|entry| {
+                    self.order.compare(&self.unescape(entry), end).is_lt()
+                })
+            }
+            Bound::Unbounded => self.tiled_len(),
+        };
+
+        self.entries_between(front, back)
     }
-}
 
-/// Error that can occur when creating a [`SortedString`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum SortedStringCreationError {
-    /// The passed haystack was not sorted.
-    NotSorted,
-    /// The passed haystack was empty.
-    EmptyHaystack,
-}
+    /// Returns the byte offset of the first entry for which `pred` returns `false`.
+    ///
+    /// Mirrors [`partition_point` of the standard
+    /// library](https://doc.rust-lang.org/std/primitive.slice.html#method.partition_point):
+    /// the haystack is assumed to be partitioned according to `pred`, with all entries
+    /// for which it returns `true` preceding all others. If `pred` holds for all
+    /// entries, the haystack's length is returned. The returned offset is always
+    /// entry-aligned, i.e. the start of an entry or the end of the haystack.
+    ///
+    /// This is the primitive underlying [`SortedString::lower_bound()`],
+    /// [`SortedString::upper_bound()`] and most other queries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::Comma;
+    /// let haystack = "a,bb,ccc,dddd";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// assert_eq!(ss.partition_point(|entry| entry.len() < 3), 5);
+    /// assert_eq!(&haystack[5..], "ccc,dddd");
+    ///
+    /// assert_eq!(ss.partition_point(|_| true), haystack.len());
+    /// assert_eq!(ss.partition_point(|_| false), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn partition_point<P>(&self, pred: P) -> usize
+    where
+        P: FnMut(&'a str) -> bool,
+    {
+        self.partition_point_tiled(pred).min(self.string.len())
+    }
 
-impl Error for SortedStringCreationError {}
+    /// Returns the byte offset of the first entry *not less* than `needle`.
+    ///
+    /// This is where `needle` would be inserted *before* any equal entries. If all
+    /// entries are less than `needle`, the haystack's length is returned. See also
+    /// [`SortedString::partition_point()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::Comma;
+    /// let haystack = "a,b,b,c";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// assert_eq!(ss.lower_bound("b"), 2);
+    /// assert_eq!(ss.lower_bound("bb"), 6);
+    /// assert_eq!(ss.lower_bound("d"), haystack.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lower_bound<U>(&self, needle: U) -> usize
+    where
+        U: AsRef<str>,
+    {
+        let needle = needle.as_ref();
+        self.partition_point(|entry| self.order.compare(&self.unescape(entry), needle).is_lt())
+    }
 
-impl Display for SortedStringCreationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
+    /// Returns the byte offset of the first entry *greater* than `needle`.
+    ///
+    /// This is where `needle` would be inserted *after* any equal entries. If no entry
+    /// is greater than `needle`, the haystack's length is returned. See also
+    /// [`SortedString::partition_point()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::Comma;
+    /// let haystack = "a,b,b,c";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// assert_eq!(ss.upper_bound("b"), 6);
+    /// assert_eq!(ss.upper_bound("0"), 0);
+    /// assert_eq!(ss.upper_bound("c"), haystack.len());
+    ///
+    /// // Counting occurrences
+    /// let count = haystack[ss.lower_bound("b")..ss.upper_bound("b")]
+    ///     .split_terminator(',')
+    ///     .count();
+    /// assert_eq!(count, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upper_bound<U>(&self, needle: U) -> usize
+    where
+        U: AsRef<str>,
+    {
+        let needle = needle.as_ref();
+        self.partition_point(|entry| self.order.compare(&self.unescape(entry), needle).is_le())
+    }
+
+    /// Returns the greatest entry *less* than `needle`, alongside its location.
+    ///
+    /// Useful to suggest the closest known entries for a needle that could not be
+    /// found, see also [`SortedString::successor()`]. Returns [`None`] if no entry is
+    /// less than `needle`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::ops::Range;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// assert_eq!(
+    ///     ss.predecessor("Angel"),
+    ///     Some((Range { start: 7, end: 12 }, "Ampel"))
+    /// );
+    /// assert_eq!(
+    ///     ss.predecessor("Ampel"),
+    ///     Some((Range { start: 0, end: 6 }, "Aachen"))
+    /// );
+    /// assert_eq!(ss.predecessor("Aachen"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn predecessor<U>(&self, needle: U) -> Option<(Range<usize>, &'a str)>
+    where
+        U: AsRef<str>,
+    {
+        let needle = needle.as_ref();
+
+        match self.partition_point_tiled(|entry| {
+            self.order.compare(&self.unescape(entry), needle).is_lt()
+        }) {
+            0 => None,
+            pos => {
+                // The separator following the predecessor, or the haystack's end.
+                let range = self.tile_around(pos - 1).entry;
+                Some((range.clone(), &self.string[range]))
+            }
+        }
+    }
+
+    /// Returns the smallest entry *greater* than `needle`, alongside its location.
+    ///
+    /// The counterpart to [`SortedString::predecessor()`]. Returns [`None`] if no entry
+    /// is greater than `needle`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::ops::Range;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// assert_eq!(
+    ///     ss.successor("Angel"),
+    ///     Some((Range { start: 13, end: 18 }, "Apfel"))
+    /// );
+    /// assert_eq!(
+    ///     ss.successor("Aachen"),
+    ///     Some((Range { start: 7, end: 12 }, "Ampel"))
+    /// );
+    /// assert_eq!(ss.successor("Apfel"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn successor<U>(&self, needle: U) -> Option<(Range<usize>, &'a str)>
+    where
+        U: AsRef<str>,
+    {
+        let needle = needle.as_ref();
+        let pos = self.partition_point_tiled(|entry| {
+            self.order.compare(&self.unescape(entry), needle).is_le()
+        });
+
+        if pos >= self.tiled_len() {
+            None
+        } else {
+            let range = self.tile_around(pos).entry;
+            Some((range.clone(), &self.string[range]))
+        }
+    }
+
+    const fn new(string: &'a str, sep: Separator<'a>, format: Format, order: O) -> Self {
+        Self {
+            string,
+            sep,
+            format,
+            order,
+        }
+    }
+
+    const fn layout(&self) -> Layout<'a> {
+        Layout {
+            string: self.string,
+            sep: self.sep,
+            format: self.format,
+        }
+    }
+
+    fn tile_around(&self, pos: usize) -> Tile {
+        self.layout().tile_around(pos)
+    }
+
+    /// Binary search within the window of entries between the tiled positions `low` and
+    /// `high`, see [`SortedString::partition_point_tiled()`].
+    ///
+    /// `cmp` reports how an entry compares to the sought-after target. `last_probe` and
+    /// `side` are reported if the window is empty to begin with.
+    fn bisect<F>(
+        &self,
+        mut cmp: F,
+        low: usize,
+        high: usize,
+        last_probe: Range<usize>,
+        side: Side,
+    ) -> SearchResult
+    where
+        F: FnMut(&'a str) -> Ordering,
+    {
+        bisect(
+            self.string.len(),
+            |pos| self.tile_around(pos),
+            // Indices are at separators and therefore always valid for slicing.
+            |entry| cmp(&self.string[entry]),
+            low..high,
+            last_probe,
+            side,
+        )
+    }
+
+    /// Searches for `needle`, which is known to not sort before the entry at tiled
+    /// position `low`.
+    ///
+    /// Probes entries at exponentially increasing distances from `low` until one
+    /// greater than `needle` is found, then bisects the remaining window.
+    fn gallop(&self, needle: &str, mut low: usize) -> SearchResult {
+        let tiled_len = self.tiled_len();
+        let mut high = tiled_len;
+
+        // Only reported if no probing occurs at all, which is only the case if the
+        // needle is already known to sort after the last entry.
+        let mut last_probe = self.tile_around(tiled_len.saturating_sub(1)).entry;
+        let mut side = Side::Right;
+
+        let mut step = 0;
+        while low + step < tiled_len {
+            let Tile { entry, tiles } = self.tile_around(low + step);
+            last_probe = entry.clone();
+
+            match self
+                .order
+                .compare(&self.unescape(&self.string[entry.clone()]), needle)
+            {
+                Ordering::Greater => {
+                    high = tiles.start;
+                    side = Side::Left;
+                    break;
+                }
+                Ordering::Equal => return Ok(entry),
+                Ordering::Less => {
+                    low = tiles.end;
+                    side = Side::Right;
+                    step = (step * 2).max(1);
+                }
+            }
+        }
+
+        self.bisect(
+            |entry| self.order.compare(&self.unescape(entry), needle),
+            low,
+            high,
+            last_probe,
+            side,
+        )
+    }
+
+    /// Returns the position of the first entry for which `pred` is `false`, assuming
+    /// all entries for which it is `true` precede all others.
+    ///
+    /// Positions are *tiled*: the last entry is treated as if it were followed by a
+    /// separator, so if `pred` holds for all entries, [`SortedString::tiled_len()`] is
+    /// returned. Otherwise, the position is the start of an entry's tiles, see
+    /// [`Tile`].
+    fn partition_point_tiled<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&'a str) -> bool,
+    {
+        let mut low = 0;
+        let mut high = self.tiled_len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let Tile { entry, tiles } = self.tile_around(mid);
+
+            if pred(&self.string[entry]) {
+                low = tiles.end;
+            } else {
+                high = tiles.start;
+            }
+        }
+
+        low
+    }
+
+    /// The haystack's length, plus that of the separator virtually following the last
+    /// entry (unless there is a trailing one), see
+    /// [`SortedString::partition_point_tiled()`].
+    fn tiled_len(&self) -> usize {
+        self.layout().tiled_len()
+    }
+
+    /// Returns the entries between the given tiled positions, see
+    /// [`SortedString::partition_point_tiled()`].
+    fn entries_between(&self, front: usize, back: usize) -> Entries<'a> {
+        self.layout().entries_between(front, back)
+    }
+
+    fn is_sorted(&self) -> bool {
+        self.iter().tuple_windows().all(|(a, b)| {
+            self.order
+                .compare(&self.unescape(a), &self.unescape(b))
+                .is_le()
+        })
+    }
+}
+
+impl<'a, O> SortedString<'a, O>
+where
+    O: PrefixOrder,
+{
+    /// Returns the byte range spanning all entries starting with `prefix`.
+    ///
+    /// As the haystack is sorted (by a [`PrefixOrder`]), all such entries are adjacent.
+    /// Finding them costs two binary searches for the boundaries, instead of a linear
+    /// scan. [`None`] is returned if no entry starts with `prefix`. The returned range
+    /// includes the separators *between* matching entries, but none before the first or
+    /// after the last one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nAngel\nAngeln\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// assert_eq!(
+    ///     ss.prefix_range("Ang"),
+    ///     Some(std::ops::Range { start: 13, end: 25 })
+    /// );
+    /// assert_eq!(&haystack[13..25], "Angel\nAngeln");
+    ///
+    /// assert_eq!(ss.prefix_range("Bau"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefix_range<U>(&self, prefix: U) -> Option<Range<usize>>
+    where
+        U: AsRef<str>,
+    {
+        self.entries_with_prefix(prefix).span()
+    }
+
+    /// Returns an iterator over all entries starting with `prefix`, in sorted order.
+    ///
+    /// See [`SortedString::prefix_range()`] for details. The entries are handed out as
+    /// slices of the haystack, so no copying occurs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Aachen\nAmpel\nAngel\nAngeln\nApfel";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// let completions: Vec<&str> = ss.entries_with_prefix("Ang").collect();
+    /// assert_eq!(completions, vec!["Angel", "Angeln"]);
+    ///
+    /// assert_eq!(ss.entries_with_prefix("Bau").next(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entries_with_prefix<U>(&self, prefix: U) -> Entries<'a>
+    where
+        U: AsRef<str>,
+    {
+        let prefix = prefix.as_ref();
+
+        let front = self.partition_point_tiled(|entry| {
+            self.order
+                .compare_prefix(&self.unescape(entry), prefix)
+                .is_lt()
+        });
+        let back = self.partition_point_tiled(|entry| {
+            self.order
+                .compare_prefix(&self.unescape(entry), prefix)
+                .is_le()
+        });
+
+        self.entries_between(front, back)
+    }
+
+    /// Returns an iterator over all entries matching a wildcard pattern, in sorted
+    /// order.
+    ///
+    /// See [`Glob`] for the syntax. Only entries starting with the pattern's literal
+    /// prefix (everything up to its first wildcard) can match, so these are narrowed
+    /// down to using binary search first, see [`SortedString::entries_with_prefix()`].
+    /// Only those are then matched against the pattern one by one. Patterns starting
+    /// with a wildcard therefore require a linear scan.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::Glob;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "ample\nankle\napple\nbat\ncat\nhat\nrewind\nrewriting";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// let words: Vec<_> = ss.entries_matching(&Glob::new("a??le")?).collect();
+    /// assert_eq!(words, vec!["ample", "ankle", "apple"]);
+    ///
+    /// let words: Vec<_> = ss.entries_matching(&Glob::new("[bc]at")?).collect();
+    /// assert_eq!(words, vec!["bat", "cat"]);
+    ///
+    /// let words: Vec<_> = ss.entries_matching(&Glob::new("re*ing")?).collect();
+    /// assert_eq!(words, vec!["rewriting"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entries_matching<'g>(&self, glob: &'g Glob) -> GlobMatches<'a, 'g> {
+        GlobMatches {
+            entries: self.entries_with_prefix(glob.literal_prefix()),
+            glob,
+        }
+    }
+
+    /// Returns an iterator over all entries containing a match of a regular
+    /// expression, in sorted order.
+    ///
+    /// Works like [`SortedString::entries_matching()`]: if the regular expression is
+    /// anchored at the start and begins with a literal, only entries starting with that
+    /// literal are tested, found using binary search. See [`RegexQuery`] for details.
+    /// Requires the `regex` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::RegexQuery;
+    ///
+    /// let sep = b4s::AsciiChar::LineFeed;
+    /// let haystack = "Apfel\nAprikose\nApril\nBaum\nKapelle";
+    /// let ss = b4s::SortedString::new_checked(haystack, sep)?;
+    ///
+    /// // Only tests `Aprikose` and `April`
+    /// let query = RegexQuery::new("^Apri(l|kosen?)$")?;
+    /// let words: Vec<_> = ss.entries_matching_regex(&query).collect();
+    /// assert_eq!(words, vec!["Aprikose", "April"]);
+    ///
+    /// // Tests all entries
+    /// let query = RegexQuery::new("pe")?;
+    /// let words: Vec<_> = ss.entries_matching_regex(&query).collect();
+    /// assert_eq!(words, vec!["Kapelle"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn entries_matching_regex<'r>(&self, query: &'r RegexQuery) -> RegexMatches<'a, 'r> {
+        RegexMatches {
+            entries: self.entries_with_prefix(query.literal_prefix()),
+            regex: query.regex(),
+        }
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl<O> SortedString<'_, O>
+where
+    O: SortOrder,
+{
+    /// Checks that the haystack is in the given normalization `form`, returning the
+    /// instance unchanged if so.
+    ///
+    /// Meant to be chained onto [`SortedString::new_checked()`] and the like. Searching
+    /// a normalized haystack is only reliable with needles in the same form, see
+    /// [`SortedString::binary_search_normalized()`]. Requires the `unicode` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{
+    ///     AsciiChar, NormalizationForm, SortedString, SortedStringCreationError,
+    /// };
+    ///
+    /// let sep = AsciiChar::LineFeed;
+    /// let ss = SortedString::new_checked("Baum\nBäume", sep)?
+    ///     .ensure_normalized(NormalizationForm::Nfc)?;
+    /// assert!(ss.binary_search("Bäume").is_ok());
+    ///
+    /// // Decomposed: `a` followed by a combining diaeresis
+    /// let ss = SortedString::new_checked("Baum\nBa\u{308}ume", sep)?;
+    /// assert_eq!(
+    ///     ss.ensure_normalized(NormalizationForm::Nfc),
+    ///     Err(SortedStringCreationError::NotNormalized(NormalizationForm::Nfc))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SortedStringCreationError::NotNormalized`] if the haystack is not in
+    /// `form`.
+    pub fn ensure_normalized(
+        self,
+        form: NormalizationForm,
+    ) -> Result<Self, SortedStringCreationError> {
+        if form.is_normalized(self.string) {
+            Ok(self)
+        } else {
+            Err(SortedStringCreationError::NotNormalized(form))
+        }
+    }
+
+    /// Searches for `needle` after converting it to the normalization `form`.
+    ///
+    /// Works like [`SortedString::binary_search()`], but finds entries regardless of
+    /// how the needle is encoded, as long as the haystack is in `form` (see
+    /// [`SortedString::ensure_normalized()`]). Normalization happens on the fly:
+    /// needles already in `form` are used as-is, and short ones are normalized on the
+    /// stack, so no heap allocation occurs for typical needles. Requires the `unicode`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, NormalizationForm, SortedString};
+    ///
+    /// let sep = AsciiChar::LineFeed;
+    /// let ss = SortedString::new_checked("Baum\nBäume", sep)?
+    ///     .ensure_normalized(NormalizationForm::Nfc)?;
+    ///
+    /// // As typed on macOS, for example
+    /// let needle = "Ba\u{308}ume";
+    /// assert!(ss.binary_search(needle).is_err());
+    /// assert_eq!(
+    ///     ss.binary_search_normalized(needle, NormalizationForm::Nfc),
+    ///     Ok(std::ops::Range { start: 5, end: 11 })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SearchError`] for more info. Its ranges refer to the haystack, so are
+    /// unaffected by normalizing the needle.
+    pub fn binary_search_normalized<U>(&self, needle: U, form: NormalizationForm) -> SearchResult
+    where
+        U: AsRef<str>,
+    {
+        form.with_normalized(needle.as_ref(), |needle| self.binary_search(needle))
+    }
+}
+
+/// Binary search within the window of tiled positions `window` of a haystack `len`
+/// bytes long, see [`SortedString::partition_point_tiled()`].
+///
+/// `tile_around` locates the [`Tile`] at a tiled position, and `cmp` reports how the
+/// entry at a location compares to the sought-after target. `last_probe` and `side`
+/// are reported if the window is empty to begin with.
+fn bisect<T, F>(
+    len: usize,
+    mut tile_around: T,
+    mut cmp: F,
+    window: Range<usize>,
+    mut last_probe: Range<usize>,
+    mut side: Side,
+) -> SearchResult
+where
+    T: FnMut(usize) -> Tile,
+    F: FnMut(Range<usize>) -> Ordering,
+{
+    let Range {
+        start: mut low,
+        end: mut high,
+    } = window;
+
+    while low < high {
+        // The lower middle, so the first probe is at half the haystack's length, not
+        // counting the trailing (virtual) separator.
+        let mid = low + (high - low - 1) / 2;
+
+        let Tile { entry, tiles } = tile_around(mid);
+        last_probe = entry.clone();
+
+        match cmp(entry.clone()) {
+            Ordering::Greater => {
+                high = tiles.start;
+                side = Side::Left;
+            }
+            Ordering::Equal => return Ok(entry),
+            Ordering::Less => {
+                low = tiles.end;
+                side = Side::Right;
+            }
+        }
+    }
+
+    Err(SearchError {
+        last_probe,
+        insertion_point: low.min(len),
+        side,
+    })
+}
+
+/// Returns the location of the entry in `haystack` containing the byte at `pos`.
+///
+/// A separator at `pos` counts towards the entry preceding it, escaped ones (see
+/// [`Format::with_escape()`]) towards the entry they are in. `pos` may be at or past
+/// the haystack's end, up to its tiled length, yielding the last entry.
+fn entry_around(haystack: &[u8], sep: &[u8], escape: Option<u8>, pos: usize) -> Range<usize> {
+    let pos = pos.min(haystack.len());
+
+    if let (&[sep], None) = (sep, escape) {
+        let is_sep = |c: &u8| *c == sep;
+
+        let start = haystack[..pos]
+            .iter()
+            .rposition(is_sep)
+            .map_or(0, |i| i + 1);
+        let end = haystack[pos..]
+            .iter()
+            .position(is_sep)
+            .map_or(haystack.len(), |i| pos + i);
+
+        return Range { start, end };
+    }
+
+    let is_sep = |i: &usize| splits_at(haystack, sep, escape, *i);
+
+    // The first separator not ending before `pos`, which might contain it.
+    let from = (pos + 1).saturating_sub(sep.len());
+    let end = (from..haystack.len())
+        .find(is_sep)
+        .unwrap_or(haystack.len());
+    let start = (0..(end + 1).saturating_sub(sep.len()))
+        .rev()
+        .find(is_sep)
+        .map_or(0, |i| i + sep.len());
+
+    Range { start, end }
+}
+
+/// Returns whether a separator at `i` splits `haystack`.
+///
+/// Like [`str::split()`], separators are matched from the left, without overlapping.
+/// Whether an occurrence splits therefore depends on the overlapping ones before it,
+/// back to the first of them, which always splits.
+fn splits_at(haystack: &[u8], sep: &[u8], escape: Option<u8>, i: usize) -> bool {
+    let occurs_at =
+        |i: usize| haystack[i..].starts_with(sep) && !format::is_escaped(haystack, escape, i);
+
+    if !occurs_at(i) {
+        return false;
+    }
+
+    let mut split = i;
+    while let Some(overlapping) =
+        (split.saturating_sub(sep.len() - 1)..split).find(|j| occurs_at(*j))
+    {
+        split = overlapping;
+    }
+
+    while split < i {
+        match (split + sep.len()..=i).find(|j| occurs_at(*j)) {
+            Some(next) => split = next,
+            None => return false,
+        }
+    }
+
+    true
+}
+
+/// A haystack, alongside everything needed to tell its entries apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Layout<'a> {
+    string: &'a str,
+    sep: Separator<'a>,
+    format: Format,
+}
+
+/// The location of an entry, alongside the tiled positions belonging to it, see
+/// [`SortedString::partition_point_tiled()`].
+///
+/// Tiles span the entry and its (possibly virtual) separator. Entries ignored by the
+/// [`Format`] have no tiles of their own: theirs belong to the closest entry before
+/// them, or for those leading the haystack, the first entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Tile {
+    entry: Range<usize>,
+    tiles: Range<usize>,
+}
+
+impl<'a> Layout<'a> {
+    const fn entries_between(self, front: usize, back: usize) -> Entries<'a> {
+        Entries {
+            layout: self,
+            front,
+            back,
+        }
+    }
+
+    fn entries(self) -> Entries<'a> {
+        self.entries_between(0, self.tiled_len())
+    }
+
+    /// The end of the tiles of the last entry, or zero if there are no entries.
+    fn tiled_len(self) -> usize {
+        if self.format.skips_entries() && self.next_entry(0).is_none() {
+            0
+        } else {
+            self.tiles_end()
+        }
+    }
+
+    fn tiles_end(self) -> usize {
+        if self.format.ignores_trailing(self.string, self.sep.as_str()) {
+            self.string.len()
+        } else {
+            self.string.len() + self.sep.len()
+        }
+    }
+
+    /// Returns the location of the entry at `raw`, or [`None`] if it is ignored, see
+    /// [`Format::entry()`].
+    fn entry(self, raw: Range<usize>) -> Option<Range<usize>> {
+        self.format.entry(self.string, self.sep.as_str(), raw)
+    }
+
+    /// Returns `entry` in its unescaped form, see [`Format::with_escape()`].
+    fn unescape(self, entry: &'a str) -> Cow<'a, str> {
+        self.format.unescape(entry, self.sep.as_str())
+    }
+
+    fn tile_around(self, pos: usize) -> Tile {
+        let raw = entry_around(
+            self.string.as_bytes(),
+            self.sep.as_bytes(),
+            self.format.escape_byte(),
+            pos,
+        );
+
+        if !self.format.skips_entries() {
+            return Tile {
+                entry: self.entry(raw.clone()).unwrap_or_default(),
+                tiles: raw.start..raw.end + self.sep.len(),
+            };
+        }
+
+        let owner = match self.entry(raw.clone()) {
+            Some(_) => Some(raw.clone()),
+            None => self
+                .previous_entry(raw.start)
+                .or_else(|| self.next_entry(raw.end + self.sep.len())),
+        };
+
+        match owner {
+            Some(owner) => Tile {
+                entry: self.entry(owner.clone()).unwrap_or_default(),
+                tiles: Range {
+                    start: self.previous_entry(owner.start).map_or(0, |_| owner.start),
+                    end: self
+                        .next_entry(owner.end + self.sep.len())
+                        .map_or(self.tiles_end(), |next| next.start),
+                },
+            },
+            // Only ignored entries, so there are no tiles to speak of.
+            None => Tile {
+                entry: self.string.len()..self.string.len(),
+                tiles: 0..0,
+            },
+        }
+    }
+
+    /// Returns the raw location of the closest entry not ignored by the [`Format`],
+    /// which ends before the given entry start.
+    fn previous_entry(self, mut start: usize) -> Option<Range<usize>> {
+        while start > 0 {
+            let raw = entry_around(
+                self.string.as_bytes(),
+                self.sep.as_bytes(),
+                self.format.escape_byte(),
+                start - 1,
+            );
+            if self.entry(raw.clone()).is_some() {
+                return Some(raw);
+            }
+            // Always make progress, even on haystacks not split the way they are built.
+            start = raw.start.min(start - 1);
+        }
+
+        None
+    }
+
+    /// Returns the raw location of the closest entry not ignored by the [`Format`],
+    /// which starts at or after the given entry start.
+    fn next_entry(self, mut start: usize) -> Option<Range<usize>> {
+        while start <= self.string.len() {
+            let raw = entry_around(
+                self.string.as_bytes(),
+                self.sep.as_bytes(),
+                self.format.escape_byte(),
+                start,
+            );
+            if self.entry(raw.clone()).is_some() {
+                return Some(raw);
+            }
+            // Always make progress, even on haystacks not split the way they are built.
+            start = (raw.end + self.sep.len()).max(start + 1);
+        }
+
+        None
+    }
+}
+
+/// Iterator over entries of a [`SortedString`], yielding slices of its haystack.
+///
+/// Created by methods such as [`SortedString::entries_with_prefix()`]. Iteration is
+/// possible from both ends.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entries<'a> {
+    layout: Layout<'a>,
+    // Tiled positions, see `SortedString::partition_point_tiled`.
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Entries<'a> {
+    /// Returns the byte range in the haystack spanned by the remaining entries, or
+    /// [`None`] if there are none left.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        if self.front < self.back {
+            Some(Range {
+                start: self.layout.tile_around(self.front).entry.start,
+                end: self.layout.tile_around(self.back - 1).entry.end,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Turns this iterator into one that also yields the entries' locations in the
+    /// haystack.
+    #[must_use]
+    pub fn with_ranges(self) -> EntriesWithRanges<'a> {
+        EntriesWithRanges { entries: self }
+    }
+
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let Tile { entry, tiles } = self.layout.tile_around(self.front);
+        self.front = tiles.end;
+        Some(entry)
+    }
+
+    fn next_back_range(&mut self) -> Option<Range<usize>> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let Tile { entry, tiles } = self.layout.tile_around(self.back - 1);
+        self.back = tiles.start;
+        Some(entry)
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_range().map(|range| &self.layout.string[range])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every entry takes up at least the tiles of its (possibly virtual) separator.
+        let tiles = self.back.saturating_sub(self.front);
+        (usize::from(tiles > 0), Some(tiles / self.layout.sep.len()))
+    }
+}
+
+impl DoubleEndedIterator for Entries<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_range()
+            .map(|range| &self.layout.string[range])
+    }
+}
+
+impl FusedIterator for Entries<'_> {}
+
+impl<'a, O> IntoIterator for &SortedString<'a, O>
+where
+    O: SortOrder,
+{
+    type Item = &'a str;
+    type IntoIter = Entries<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over entries of a [`SortedString`] alongside their locations.
+///
+/// Created by [`SortedString::iter_with_ranges()`] and [`Entries::with_ranges()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntriesWithRanges<'a> {
+    entries: Entries<'a>,
+}
+
+impl<'a> Iterator for EntriesWithRanges<'a> {
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.entries.next_range()?;
+        Some((range.clone(), &self.entries.layout.string[range]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for EntriesWithRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let range = self.entries.next_back_range()?;
+        Some((range.clone(), &self.entries.layout.string[range]))
+    }
+}
+
+impl FusedIterator for EntriesWithRanges<'_> {}
+
+/// Error that can occur when creating a [`SortedString`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SortedStringCreationError {
+    /// The passed haystack was not sorted.
+    NotSorted,
+    /// The passed haystack was empty.
+    EmptyHaystack,
+    /// The passed haystack was not in the required normalization form. Requires the
+    /// `unicode` feature.
+    #[cfg(feature = "unicode")]
+    NotNormalized(NormalizationForm),
+    /// Occurrences of the multi-byte separator overlap in the passed haystack, so
+    /// entries cannot be told apart, e.g. `||` in `a|||b`.
+    AmbiguousSeparator,
+    /// The escape byte of the passed [`Format`] is part of the separator, so escapes
+    /// cannot be told apart from separators.
+    EscapeInSeparator,
+}
+
+impl Error for SortedStringCreationError {}
+
+impl Display for SortedStringCreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
             Self::NotSorted => write!(f, "The provided string is not sorted."),
             Self::EmptyHaystack => write!(f, "The provided string is empty."),
+            #[cfg(feature = "unicode")]
+            Self::NotNormalized(form) => write!(f, "The provided string is not in {form}."),
+            Self::AmbiguousSeparator => {
+                write!(f, "The provided string contains overlapping separators.")
+            }
+            Self::EscapeInSeparator => {
+                write!(f, "The provided escape is part of the separator.")
+            }
         }
     }
 }
@@ -540,7 +2370,14 @@ mod tests {
     #[rstest]
     #[case(Box::new(SortedStringCreationError::NotSorted))]
     #[case(Box::new(SortedStringCreationError::EmptyHaystack))]
-    #[case(Box::new(SearchError(Range { start: 0, end: 1 })))]
+    #[cfg_attr(
+        feature = "unicode",
+        case(Box::new(SortedStringCreationError::NotNormalized(NormalizationForm::Nfc)))
+    )]
+    #[case(Box::new(SortedStringCreationError::AmbiguousSeparator))]
+    #[case(Box::new(SortedStringCreationError::EscapeInSeparator))]
+    #[case(Box::new(GlobError::DanglingEscape))]
+    #[case(Box::new(SearchError { last_probe: Range { start: 0, end: 1 }, insertion_point: 2, side: Side::Right }))]
     fn test_error_trait_implementations_are_present(#[case] err: Box<dyn Error>) {
         assert!(!err.to_string().is_empty());
     }
@@ -0,0 +1,96 @@
+//! Support for [Unicode normalization forms](https://unicode.org/reports/tr15/).
+
+use std::fmt::Display;
+
+/// A [Unicode normalization form](https://unicode.org/reports/tr15/#Norm_Forms).
+///
+/// The same text can be encoded in different ways: `ä` is either a single, precomposed
+/// [`char`] (`U+00E4`), or an `a` followed by a combining diaeresis (`U+0308`). These
+/// look identical but compare unequal, so a haystack and its needles need to agree on a
+/// form for searches to succeed. Most text is in NFC, but e.g. file names on macOS
+/// commonly are in NFD.
+///
+/// Requires the `unicode` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NormalizationForm {
+    /// Canonical decomposition, followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility decomposition, followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Display for NormalizationForm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nfc => write!(f, "NFC"),
+            Self::Nfd => write!(f, "NFD"),
+            Self::Nfkc => write!(f, "NFKC"),
+            Self::Nfkd => write!(f, "NFKD"),
+        }
+    }
+}
+
+/// Needles normalizing to at most this many bytes are normalized on the stack.
+const INLINE_NEEDLE_CAPACITY: usize = 64;
+
+impl NormalizationForm {
+    /// Returns whether `s` is in this normalization form.
+    pub(crate) fn is_normalized(self, s: &str) -> bool {
+        use unicode_normalization::{is_nfc, is_nfd, is_nfkc, is_nfkd};
+
+        match self {
+            Self::Nfc => is_nfc(s),
+            Self::Nfd => is_nfd(s),
+            Self::Nfkc => is_nfkc(s),
+            Self::Nfkd => is_nfkd(s),
+        }
+    }
+
+    fn normalize(self, s: &str) -> impl Iterator<Item = char> + '_ {
+        use itertools::Either;
+        use unicode_normalization::UnicodeNormalization;
+
+        match self {
+            Self::Nfc => Either::Left(s.nfc()),
+            Self::Nfd => Either::Right(s.nfd()),
+            Self::Nfkc => Either::Left(s.nfkc()),
+            Self::Nfkd => Either::Right(s.nfkd()),
+        }
+    }
+
+    /// Calls `f` with `s` converted to this normalization form.
+    ///
+    /// Only allocates if `s` is not normalized already and its normalized form exceeds
+    /// [`INLINE_NEEDLE_CAPACITY`].
+    pub(crate) fn with_normalized<R>(self, s: &str, f: impl FnOnce(&str) -> R) -> R {
+        if self.is_normalized(s) {
+            return f(s);
+        }
+
+        let mut buf = [0; INLINE_NEEDLE_CAPACITY];
+        let mut len = 0;
+        let mut chars = self.normalize(s);
+
+        while let Some(c) = chars.next() {
+            if len + c.len_utf8() > buf.len() {
+                let mut spilled = String::with_capacity(2 * buf.len());
+                spilled.push_str(inline_str(&buf[..len]));
+                spilled.push(c);
+                spilled.extend(chars);
+                return f(&spilled);
+            }
+
+            len += c.encode_utf8(&mut buf[len..]).len();
+        }
+
+        f(inline_str(&buf[..len]))
+    }
+}
+
+fn inline_str(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).expect("buffer only ever holds whole, encoded `char`s")
+}
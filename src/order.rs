@@ -0,0 +1,527 @@
+//! Orderings a [`SortedString`](crate::SortedString) can be sorted by.
+
+use itertools::Either;
+use std::cmp::Ordering;
+
+/// An ordering of entries, according to which a haystack is sorted.
+///
+/// Validation in [`SortedString::new_checked_with_order()`], sorting in
+/// [`SortedString::sort_with_order()`] and all searches of a
+/// [`SortedString`] use the same ordering, which is part of its type. They therefore
+/// agree by construction: a haystack sorted one way cannot accidentally be searched
+/// another way, which would silently produce misses.
+///
+/// [`SortedString`]: crate::SortedString
+/// [`SortedString::new_checked_with_order()`]: crate::SortedString::new_checked_with_order
+/// [`SortedString::sort_with_order()`]: crate::SortedString::sort_with_order
+///
+/// # Example: User-Defined Ordering
+///
+/// Plain functions and closures comparing two [`str`]s are orderings as well, but types
+/// implementing this trait can be named, stored in `static`s and implement common
+/// traits.
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::cmp::Ordering;
+///
+/// /// Orders entries by their last character.
+/// #[derive(Debug, Clone, Copy)]
+/// struct ByLastChar;
+///
+/// impl b4s::SortOrder for ByLastChar {
+///     fn compare(&self, a: &str, b: &str) -> Ordering {
+///         a.chars().next_back().cmp(&b.chars().next_back())
+///     }
+/// }
+///
+/// let sep = b4s::AsciiChar::Comma;
+/// let haystack = "ba,ab,dc";
+/// let ss = b4s::SortedString::new_checked_with_order(haystack, sep, ByLastChar)?;
+///
+/// assert!(ss.binary_search("ab").is_ok());
+/// # Ok(())
+/// # }
+/// ```
+pub trait SortOrder {
+    /// Compares two entries, returning how `a` orders relative to `b`.
+    fn compare(&self, a: &str, b: &str) -> Ordering;
+}
+
+impl<F> SortOrder for F
+where
+    F: Fn(&str, &str) -> Ordering,
+{
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// An ordering under which all entries starting with a given prefix are adjacent.
+///
+/// Required for prefix queries such as
+/// [`SortedString::entries_with_prefix()`](crate::SortedString::entries_with_prefix).
+/// Not all orderings qualify: under [`LengthFirst`], entries sharing a prefix are
+/// scattered throughout the haystack.
+pub trait PrefixOrder: SortOrder {
+    /// Compares `entry` to the block of all entries starting with `prefix`.
+    ///
+    /// Returns [`Ordering::Equal`] if `entry` starts with `prefix`, otherwise whether
+    /// `entry` sorts before ([`Ordering::Less`]) or after ([`Ordering::Greater`]) that
+    /// block.
+    fn compare_prefix(&self, entry: &str, prefix: &str) -> Ordering;
+}
+
+/// The default ordering: lexicographic by bytes, as implemented by [`str`]'s [`Ord`].
+///
+/// For UTF-8, this is the same as ordering lexicographically by [`char`]s (Unicode code
+/// points). It is what [`slice::sort`] produces for `&str`s, and what most tools (e.g.
+/// `sort` with `LC_ALL=C`) produce for files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytewise;
+
+impl SortOrder for Bytewise {
+    #[inline]
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl PrefixOrder for Bytewise {
+    #[inline]
+    fn compare_prefix(&self, entry: &str, prefix: &str) -> Ordering {
+        if entry.starts_with(prefix) {
+            Ordering::Equal
+        } else {
+            entry.cmp(prefix)
+        }
+    }
+}
+
+/// The reverse of another ordering, e.g. descending instead of ascending bytes.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{Bytewise, Reversed};
+///
+/// let sep = b4s::AsciiChar::Comma;
+/// let haystack = "c,b,a";
+/// let order = Reversed(Bytewise);
+/// let ss = b4s::SortedString::new_checked_with_order(haystack, sep, order)?;
+///
+/// assert_eq!(ss.binary_search("a"), Ok(std::ops::Range { start: 4, end: 5 }));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Reversed<O = Bytewise>(pub O);
+
+impl<O> SortOrder for Reversed<O>
+where
+    O: SortOrder,
+{
+    #[inline]
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+impl<O> PrefixOrder for Reversed<O>
+where
+    O: PrefixOrder,
+{
+    #[inline]
+    fn compare_prefix(&self, entry: &str, prefix: &str) -> Ordering {
+        self.0.compare_prefix(entry, prefix).reverse()
+    }
+}
+
+/// Orders entries by their length in [`char`]s first, and lexicographically second.
+///
+/// Also known as [shortlex order](https://en.wikipedia.org/wiki/Shortlex_order).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sep = b4s::AsciiChar::Comma;
+/// let haystack = "b,z,ab,ba,aaa";
+/// let order = b4s::LengthFirst;
+/// let ss = b4s::SortedString::new_checked_with_order(haystack, sep, order)?;
+///
+/// assert_eq!(ss.binary_search("ba"), Ok(std::ops::Range { start: 7, end: 9 }));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LengthFirst;
+
+impl SortOrder for LengthFirst {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        a.chars()
+            .count()
+            .cmp(&b.chars().count())
+            .then_with(|| a.cmp(b))
+    }
+}
+
+/// Orders entries lexicographically by their [`char`]s, read from the end.
+///
+/// Also known as *retrograde* or reverse dictionary order: entries ending alike are
+/// adjacent, e.g. all entries ending in `-ung`. Comparisons read [`char`]s backwards on
+/// the fly, without allocating reversed copies. Used by
+/// [`SuffixSortedString`](crate::SuffixSortedString) for suffix queries.
+///
+/// # Example
+///
+/// ```
+/// use b4s::{AsciiChar, Retrograde, SortedString};
+///
+/// let unsorted_haystack = "Zeitung,Hoffnung,Gesundheit,Bahn";
+/// let sep = AsciiChar::Comma;
+/// let haystack = SortedString::sort_with_order(unsorted_haystack, sep, &Retrograde);
+/// assert_eq!(haystack, "Hoffnung,Zeitung,Bahn,Gesundheit");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Retrograde;
+
+impl Retrograde {
+    /// Compares `entry` to the block of all entries ending with `suffix`, see
+    /// [`PrefixOrder::compare_prefix()`].
+    pub(crate) fn compare_suffix(entry: &str, suffix: &str) -> Ordering {
+        compare_prefix_by(entry.chars().rev(), suffix.chars().rev())
+    }
+}
+
+impl SortOrder for Retrograde {
+    #[inline]
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        a.chars().rev().cmp(b.chars().rev())
+    }
+}
+
+/// Orders entries [naturally](https://en.wikipedia.org/wiki/Natural_sort_order), with
+/// runs of digits compared by their numeric value.
+///
+/// Lists like `file2`, `file10` or `v1.9.0`, `v1.10.0` sort as humans expect, without
+/// zero-padding numbers. Everything but ASCII digits compares like [`Bytewise`].
+/// Numbers of any length are supported, as they are compared without being parsed.
+/// Numbers differing only in leading zeros (`7` and `007`) are ordered by their bytes,
+/// so only identical entries compare equal.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, Natural, SortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+/// let unsorted_haystack = "v1.10.0\nv1.9.0\nv1.9.10\nv1.9.2";
+/// let haystack = SortedString::sort_with_order(unsorted_haystack, sep, &Natural);
+/// assert_eq!(haystack, "v1.9.0\nv1.9.2\nv1.9.10\nv1.10.0");
+///
+/// let haystack = "file1\nfile2\nfile10";
+/// let ss = SortedString::new_checked_with_order(haystack, sep, Natural)?;
+/// assert_eq!(ss.binary_search("file10"), Ok(std::ops::Range { start: 12, end: 18 }));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Natural;
+
+impl SortOrder for Natural {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        NaturalTokens(a)
+            .cmp(NaturalTokens(b))
+            .then_with(|| a.cmp(b))
+    }
+}
+
+/// Splits a [`str`] into single [`char`]s and runs of ASCII digits.
+struct NaturalTokens<'a>(&'a str);
+
+impl<'a> Iterator for NaturalTokens<'a> {
+    type Item = NaturalToken<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.0.chars().next()?;
+
+        if c.is_ascii_digit() {
+            let end = self
+                .0
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.0.len());
+            let (digits, rest) = self.0.split_at(end);
+            self.0 = rest;
+
+            Some(NaturalToken::Number(digits.trim_start_matches('0')))
+        } else {
+            self.0 = &self.0[c.len_utf8()..];
+
+            Some(NaturalToken::Char(c))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum NaturalToken<'a> {
+    /// Digits without leading zeros.
+    Number(&'a str),
+    /// Any [`char`] but an ASCII digit.
+    Char(char),
+}
+
+impl Ord for NaturalToken<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            // Without leading zeros, longer numbers are greater.
+            (Self::Number(a), Self::Number(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            // All digits are adjacent, so any stands in for the number.
+            (Self::Number(_), Self::Char(c)) => '0'.cmp(c),
+            (Self::Char(c), Self::Number(_)) => c.cmp(&'0'),
+            (Self::Char(a), Self::Char(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for NaturalToken<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders entries lexicographically by their lowercased [`char`]s, ignoring case.
+///
+/// Lowercasing happens on the fly, without allocating. It is [`char::to_lowercase()`]
+/// plus a few selected folds: `ß` and `ẞ` compare like `ss`, final `ς` like `σ`, long
+/// `ſ` like `s`, and the ligatures `ﬀ`, `ﬁ` and `ﬂ` like the letters they join. This
+/// approximates full [case
+/// folding](https://www.w3.org/TR/charmod-norm/#definitionCaseFolding), but misses some
+/// of its mappings, such as those for Cherokee, `İ` or Greek symbols like `ϐ`. Entries
+/// differing only in case compare equal, so searching for any casing finds the entry in
+/// its original case. The relative order of such entries is irrelevant for searching.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, CaseInsensitive, SortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+/// let unsorted_haystack = "Zaun\napfel\nÄpfel\nBaum";
+/// let order = CaseInsensitive;
+/// let haystack = SortedString::sort_with_order(unsorted_haystack, sep, &order);
+/// assert_eq!(haystack, "apfel\nBaum\nZaun\nÄpfel");
+///
+/// let ss = SortedString::new_checked_with_order(&haystack, sep, order)?;
+///
+/// let m = ss.search("APFEL")?;
+/// assert_eq!(m.entry, "apfel");
+///
+/// let m = ss.search("äPFEL")?;
+/// assert_eq!(m.entry, "Äpfel");
+///
+/// assert_eq!(ss.entries_with_prefix("b").collect::<Vec<_>>(), vec!["Baum"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CaseInsensitive;
+
+impl CaseInsensitive {
+    /// Lowercases a single [`char`], applying selected folds, which might result in
+    /// multiple ones.
+    fn fold(c: char) -> impl Iterator<Item = char> {
+        let special = match c {
+            'ß' | 'ẞ' => Some("ss"),
+            'ς' => Some("σ"),
+            'ſ' => Some("s"),
+            'ﬀ' => Some("ff"),
+            'ﬁ' => Some("fi"),
+            'ﬂ' => Some("fl"),
+            _ => None,
+        };
+
+        match special {
+            Some(folded) => Either::Left(folded.chars()),
+            None => Either::Right(c.to_lowercase()),
+        }
+    }
+
+    fn folded(s: &str) -> impl Iterator<Item = char> + '_ {
+        s.chars().flat_map(Self::fold)
+    }
+}
+
+impl SortOrder for CaseInsensitive {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        Self::folded(a).cmp(Self::folded(b))
+    }
+}
+
+impl PrefixOrder for CaseInsensitive {
+    fn compare_prefix(&self, entry: &str, prefix: &str) -> Ordering {
+        compare_prefix_by(Self::folded(entry), Self::folded(prefix))
+    }
+}
+
+/// Orders entries lexicographically by their [`char`]s, ignoring diacritics.
+///
+/// Entries are [canonically decomposed](https://unicode.org/reports/tr15/) and stripped
+/// of combining marks on the fly, without allocating: `é` compares like `e`, and `Ä`
+/// like `A`. Input in mixed [NFC](https://unicode.org/reports/tr15/#Norm_Forms) and NFD
+/// is handled alike. Characters without a decomposition, such as `ß` or `ø`, are mapped
+/// using a table of *folds* first, which can be configured using
+/// [`DiacriticInsensitive::with_folds()`]. Case is preserved.
+///
+/// As several entries may fold to the same key (`cafe` and `café`, say), use
+/// [`SortedString::search_all()`](crate::SortedString::search_all) to find all of them.
+///
+/// Requires the `unicode` feature.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, DiacriticInsensitive, SortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+/// let order = DiacriticInsensitive::new();
+/// let unsorted_haystack = "Straße\ncafé\nÄpfel\nBaum\ncafe";
+/// let haystack = SortedString::sort_with_order(unsorted_haystack, sep, &order);
+/// assert_eq!(haystack, "Äpfel\nBaum\nStraße\ncafé\ncafe");
+///
+/// let ss = SortedString::new_checked_with_order(&haystack, sep, order)?;
+///
+/// assert_eq!(ss.search("Apfel")?.entry, "Äpfel");
+/// assert_eq!(ss.search("Strasse")?.entry, "Straße");
+/// assert_eq!(ss.search_all("cafe").collect::<Vec<_>>(), vec!["café", "cafe"]);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DiacriticInsensitive {
+    folds: &'static [(char, &'static str)],
+}
+
+#[cfg(feature = "unicode")]
+impl DiacriticInsensitive {
+    /// Folds applied by [`DiacriticInsensitive::new()`], for letters which do not
+    /// decompose into a base letter and combining marks.
+    pub const DEFAULT_FOLDS: &'static [(char, &'static str)] = &[
+        ('ß', "ss"),
+        ('ẞ', "SS"),
+        ('æ', "ae"),
+        ('Æ', "AE"),
+        ('œ', "oe"),
+        ('Œ', "OE"),
+        ('ø', "o"),
+        ('Ø', "O"),
+        ('đ', "d"),
+        ('Đ', "D"),
+        ('ł', "l"),
+        ('Ł', "L"),
+        ('ı', "i"),
+    ];
+
+    /// Creates the ordering using [`DiacriticInsensitive::DEFAULT_FOLDS`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_folds(Self::DEFAULT_FOLDS)
+    }
+
+    /// Creates the ordering using custom folds, replacing the default ones.
+    ///
+    /// Each fold maps a [`char`] to the string it should compare as. Folds apply to
+    /// composed characters *before* diacritics are stripped, so they can also be used
+    /// to override the stripping for specific characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, DiacriticInsensitive, SortedString};
+    ///
+    /// // German transliteration, where umlauts are spelled out.
+    /// static FOLDS: &[(char, &str)] =
+    ///     &[('ä', "ae"), ('ö', "oe"), ('ü', "ue"), ('ß', "ss")];
+    /// let order = DiacriticInsensitive::with_folds(FOLDS);
+    ///
+    /// let sep = AsciiChar::LineFeed;
+    /// let haystack = "Bäume\nBaum\nBaumhaus";
+    /// let ss = SortedString::new_checked_with_order(haystack, sep, order)?;
+    ///
+    /// assert_eq!(ss.search("Baeume")?.entry, "Bäume");
+    /// assert!(ss.search("Baume").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn with_folds(folds: &'static [(char, &'static str)]) -> Self {
+        Self { folds }
+    }
+
+    fn fold(self, c: char) -> impl Iterator<Item = char> {
+        match self.folds.iter().find(|(from, _)| *from == c) {
+            Some((_, to)) => Either::Left(to.chars()),
+            None => Either::Right(std::iter::once(c)),
+        }
+    }
+
+    fn folded(self, s: &str) -> impl Iterator<Item = char> + '_ {
+        use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+        // Compose first, so folds apply regardless of the input's normalization form.
+        s.chars()
+            .nfc()
+            .flat_map(move |c| self.fold(c))
+            .nfd()
+            .filter(|c| !is_combining_mark(*c))
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl Default for DiacriticInsensitive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl SortOrder for DiacriticInsensitive {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.folded(a).cmp(self.folded(b))
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl PrefixOrder for DiacriticInsensitive {
+    fn compare_prefix(&self, entry: &str, prefix: &str) -> Ordering {
+        compare_prefix_by(self.folded(entry), self.folded(prefix))
+    }
+}
+
+/// Implements [`PrefixOrder::compare_prefix()`] for orderings comparing sequences of
+/// items lexicographically.
+fn compare_prefix_by<I, T>(entry: I, prefix: I) -> Ordering
+where
+    I: Iterator<Item = T>,
+    T: Ord,
+{
+    let mut entry = entry;
+
+    for p in prefix {
+        match entry.next() {
+            // Entry is a proper prefix of the prefix, so sorts before all entries
+            // starting with it.
+            None => return Ordering::Less,
+            Some(e) if e == p => {}
+            Some(e) => return e.cmp(&p),
+        }
+    }
+
+    Ordering::Equal
+}
@@ -0,0 +1,126 @@
+//! Regular expression queries, narrowed down by their literal prefix.
+
+use crate::Entries;
+use regex::Regex;
+use regex_syntax::hir::{
+    literal::{ExtractKind, Extractor},
+    Look,
+};
+use std::iter::FusedIterator;
+
+/// A regular expression, to find entries with
+/// [`SortedString::entries_matching_regex()`](crate::SortedString::entries_matching_regex).
+///
+/// Wraps a [`Regex`], alongside the literal prefix all entries it can match start with.
+/// Only regular expressions anchored at the start (`^`) have such a prefix. For others,
+/// any entry might contain a match, requiring a linear scan.
+///
+/// Requires the `regex` feature, which needs Rust 1.65, above the crate's minimum
+/// supported Rust version.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::RegexQuery;
+///
+/// let query = RegexQuery::new("^Ap(fel|ril)$")?;
+/// assert_eq!(query.literal_prefix(), "Ap");
+/// assert!(query.regex().is_match("April"));
+///
+/// // Not anchored, so may match anywhere within entries.
+/// let query = RegexQuery::new("Ap(fel|ril)$")?;
+/// assert_eq!(query.literal_prefix(), "");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexQuery {
+    regex: Regex,
+    literal_prefix: String,
+}
+
+impl RegexQuery {
+    /// Compiles a regular expression, see [`Regex::new()`] for the syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`regex::Error`] if the pattern is invalid.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = Regex::new(pattern)?;
+        let literal_prefix = literal_prefix(pattern).unwrap_or_default();
+
+        Ok(Self {
+            regex,
+            literal_prefix,
+        })
+    }
+
+    /// The compiled regular expression.
+    #[must_use]
+    pub const fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// The [`str`] all matching entries start with, which is empty if unknown.
+    #[must_use]
+    pub fn literal_prefix(&self) -> &str {
+        &self.literal_prefix
+    }
+}
+
+/// Extracts the longest literal all matches of `pattern` start with, if it is anchored
+/// at the start.
+fn literal_prefix(pattern: &str) -> Option<String> {
+    // Parses with the same defaults as `Regex::new`.
+    let hir = regex_syntax::parse(pattern).ok()?;
+
+    if !hir.properties().look_set_prefix().contains(Look::Start) {
+        return None;
+    }
+
+    let literals = Extractor::new().kind(ExtractKind::Prefix).extract(&hir);
+    let prefix = literals.longest_common_prefix()?;
+
+    // Literals might have been truncated in the middle of a `char`.
+    let prefix = match std::str::from_utf8(prefix) {
+        Ok(prefix) => prefix,
+        Err(e) => std::str::from_utf8(&prefix[..e.valid_up_to()]).ok()?,
+    };
+
+    Some(prefix.to_owned())
+}
+
+/// Iterator over all entries matching a [`RegexQuery`], see
+/// [`SortedString::entries_matching_regex()`](crate::SortedString::entries_matching_regex).
+#[derive(Debug, Clone)]
+pub struct RegexMatches<'a, 'r> {
+    pub(crate) entries: Entries<'a>,
+    pub(crate) regex: &'r Regex,
+}
+
+impl<'a> Iterator for RegexMatches<'a, '_> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let regex = self.regex;
+        let layout = self.entries.layout;
+        self.entries
+            .find(|entry| regex.is_match(&layout.unescape(entry)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.entries.size_hint().1)
+    }
+}
+
+impl DoubleEndedIterator for RegexMatches<'_, '_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let regex = self.regex;
+        let layout = self.entries.layout;
+        self.entries
+            .rfind(|entry| regex.is_match(&layout.unescape(entry)))
+    }
+}
+
+impl FusedIterator for RegexMatches<'_, '_> {}
@@ -0,0 +1,178 @@
+//! Separators between entries of a haystack.
+
+use crate::format;
+use ascii::AsciiChar;
+use std::str::Utf8Error;
+
+/// The separator between entries of a haystack, such as a line feed.
+///
+/// Usually a single [`AsciiChar`], which converts into a [`Separator`] and is accepted
+/// wherever one is expected. Multi-byte separators, such as `\r\n` in files coming from
+/// Windows or `||` in exports, are created using [`Separator::new()`].
+///
+/// Any separator is valid UTF-8 itself. As UTF-8 is
+/// [self-synchronizing](https://en.wikipedia.org/wiki/UTF-8#Encoding), it can therefore
+/// only ever be found at [char boundaries](str::is_char_boundary) of the haystack,
+/// which can still be scanned as plain bytes.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{Separator, SortedString};
+///
+/// let sep = Separator::new("\r\n").expect("not empty");
+/// let haystack = "Aachen\r\nAmpel\r\nApfel";
+/// let ss = SortedString::new_checked(haystack, sep)?;
+///
+/// assert_eq!(ss.binary_search("Ampel"), Ok(std::ops::Range { start: 8, end: 13 }));
+/// assert_eq!(SortedString::sort("b·a", Separator::new("·").unwrap()), "a·b");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Separator<'s>(Repr<'s>);
+
+// Single bytes are by far the most common, and are kept inline.
+#[allow(variant_size_differences)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Repr<'s> {
+    /// Always within the ASCII range.
+    Byte(u8),
+    /// Always longer than a single byte.
+    Str(&'s str),
+}
+
+impl<'s> Separator<'s> {
+    /// Carriage return followed by line feed, as in files coming from Windows.
+    pub const CRLF: Separator<'static> = Separator(Repr::Str("\r\n"));
+
+    /// Creates a separator from a [`str`], returning [`None`] if it is empty.
+    ///
+    /// A single [`char`] of ASCII is equivalent to the corresponding [`AsciiChar`]:
+    ///
+    /// ```
+    /// use b4s::{AsciiChar, Separator};
+    ///
+    /// assert_eq!(Separator::new(","), Some(Separator::from(AsciiChar::Comma)));
+    /// ```
+    #[must_use]
+    pub const fn new(sep: &'s str) -> Option<Self> {
+        match sep.as_bytes() {
+            [] => None,
+            // A single byte of UTF-8 is always ASCII.
+            [byte] => Some(Self(Repr::Byte(*byte))),
+            _ => Some(Self(Repr::Str(sep))),
+        }
+    }
+
+    /// Creates a separator from raw bytes, returning [`None`] if they are empty or not
+    /// valid UTF-8.
+    ///
+    /// ```
+    /// use b4s::Separator;
+    ///
+    /// assert_eq!(Separator::from_bytes(b"||"), Separator::new("||"));
+    /// // Part of a multi-byte `char` only, which might be found inside of others:
+    /// assert_eq!(Separator::from_bytes(&[0xC2]), None);
+    /// assert_eq!(Separator::from_bytes(b""), None);
+    /// ```
+    #[must_use]
+    pub fn from_bytes(sep: &'s [u8]) -> Option<Self> {
+        std::str::from_utf8(sep).ok().and_then(Self::new)
+    }
+
+    /// Creates a separator from a single [`AsciiChar`].
+    #[must_use]
+    pub const fn from_ascii(sep: AsciiChar) -> Self {
+        Self(Repr::Byte(sep.as_byte()))
+    }
+
+    /// Creates a separator from a single byte, failing unless it is valid UTF-8 on its
+    /// own, i.e. ASCII.
+    pub(crate) fn from_utf8_byte(byte: u8) -> Result<Self, Utf8Error> {
+        std::str::from_utf8(&[byte])?;
+        Ok(Self(Repr::Byte(byte)))
+    }
+
+    /// Returns the separator as bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Byte(byte) => std::slice::from_ref(byte),
+            Repr::Str(sep) => sep.as_bytes(),
+        }
+    }
+
+    /// Returns the separator as a [`str`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            // Never fails, as the byte is ASCII.
+            Repr::Byte(_) => std::str::from_utf8(self.as_bytes()).unwrap_or_default(),
+            Repr::Str(sep) => sep,
+        }
+    }
+
+    /// Returns the separator as an [`AsciiChar`], if it is a single byte.
+    pub(crate) fn as_ascii(&self) -> Option<AsciiChar> {
+        match self.0 {
+            Repr::Byte(byte) => AsciiChar::from_ascii(byte).ok(),
+            Repr::Str(_) => None,
+        }
+    }
+
+    /// The length of the separator in bytes, which is never zero.
+    pub(crate) fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns whether `haystack` splits into the same entries, no matter whether
+    /// overlapping occurrences of the separator are matched from the left, as
+    /// [`str::split()`] does, or from the right.
+    ///
+    /// This is not the case for a separator of `||` in `a|||b`, where it is unclear
+    /// whether the second entry is `|b` or the first one `a|`. In `a||||b`, both ways
+    /// find an empty entry between `a` and `b`. Occurrences preceded by `escape` are
+    /// not separators, see [`Format::with_escape()`](crate::Format::with_escape).
+    pub(crate) fn splits_unambiguously(&self, haystack: &str, escape: Option<u8>) -> bool {
+        let sep = self.as_bytes();
+        if sep.len() == 1 {
+            return true;
+        }
+
+        let haystack = haystack.as_bytes();
+        let occurrences: Vec<usize> = haystack
+            .windows(sep.len())
+            .enumerate()
+            .filter(|(i, window)| *window == sep && !format::is_escaped(haystack, escape, *i))
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut from_left: Vec<usize> = Vec::new();
+        for &i in &occurrences {
+            if from_left.last().map_or(true, |last| last + sep.len() <= i) {
+                from_left.push(i);
+            }
+        }
+
+        let mut from_right: Vec<usize> = Vec::new();
+        for &i in occurrences.iter().rev() {
+            if from_right
+                .last()
+                .map_or(true, |last| i + sep.len() <= *last)
+            {
+                from_right.push(i);
+            }
+        }
+        from_right.reverse();
+
+        from_left == from_right
+    }
+}
+
+impl From<AsciiChar> for Separator<'_> {
+    fn from(sep: AsciiChar) -> Self {
+        Self::from_ascii(sep)
+    }
+}
@@ -0,0 +1,164 @@
+//! Suffix queries, through a companion haystack sorted by entries' ends.
+
+use crate::{
+    Entries, Retrograde, SearchResult, Separator, SortedString, SortedStringCreationError,
+};
+use ascii::AsciiChar;
+use std::{fmt::Display, ops::Range};
+
+/// A [`SortedString`] for suffix queries, such as finding all entries ending in `-ung`.
+///
+/// Prefix queries rely on entries sharing a prefix being adjacent, which is the case in
+/// regular (lexicographic) order. For suffixes, the haystack needs to be in
+/// [`Retrograde`] order instead, i.e. sorted by entries read backwards. This *companion
+/// haystack* contains the very same entries as a regular one, only ordered differently.
+/// It is built using [`SuffixSortedString::sort()`], at runtime or ahead of time (e.g.
+/// in a build script, for use with [`include_str!`]).
+///
+/// Entries are stored forwards, so all queries hand out slices of the haystack, as
+/// usual. Comparisons read entries backwards on the fly, so searching does not allocate
+/// either.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use b4s::{AsciiChar, SuffixSortedString};
+///
+/// let sep = AsciiChar::LineFeed;
+/// let haystack = "Bahn\nFreiheit\nGesundheit\nHoffnung\nZeitung";
+///
+/// let companion = SuffixSortedString::sort(haystack, sep);
+/// let ss = SuffixSortedString::new_checked(&companion, sep)?;
+///
+/// let words: Vec<_> = ss.entries_with_suffix("ung").collect();
+/// assert_eq!(words, vec!["Hoffnung", "Zeitung"]);
+///
+/// let words: Vec<_> = ss.entries_with_suffix("heit").collect();
+/// assert_eq!(words, vec!["Gesundheit", "Freiheit"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SuffixSortedString<'a>(SortedString<'a, Retrograde>);
+
+impl<'a> SuffixSortedString<'a> {
+    /// Creates a new instance from a companion haystack, performing sanity checks.
+    ///
+    /// See [`SortedString::new_checked()`], but the haystack has to be in
+    /// [`Retrograde`] order, as produced by [`SuffixSortedString::sort()`].
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SortedString::new_checked()`].
+    pub fn new_checked<S>(haystack: &'a str, sep: S) -> Result<Self, SortedStringCreationError>
+    where
+        S: Into<Separator<'a>>,
+    {
+        SortedString::new_checked_with_order(haystack, sep, Retrograde).map(Self)
+    }
+
+    /// Creates a new instance from a companion haystack, without performing sanity
+    /// checks.
+    ///
+    /// See [`SortedString::new_unchecked()`]. Usable in `const` contexts, e.g. with a
+    /// companion haystack generated ahead of time:
+    ///
+    /// ```
+    /// use b4s::{AsciiChar, SuffixSortedString};
+    ///
+    /// static SS: SuffixSortedString =
+    ///     SuffixSortedString::new_unchecked("Bahn,Zeit", AsciiChar::Comma);
+    ///
+    /// assert_eq!(SS.entries_with_suffix("it").next(), Some("Zeit"));
+    /// ```
+    #[must_use]
+    pub const fn new_unchecked(string: &'a str, sep: AsciiChar) -> Self {
+        Self(SortedString::new_unchecked_with_order(
+            string, sep, Retrograde,
+        ))
+    }
+
+    /// Builds a companion haystack from a haystack in any order, returning an owned
+    /// version.
+    ///
+    /// See [`SortedString::sort()`].
+    #[must_use]
+    pub fn sort<'s, S>(string: &str, sep: S) -> String
+    where
+        S: Into<Separator<'s>>,
+    {
+        SortedString::sort_with_order(string, sep, &Retrograde)
+    }
+
+    /// Returns an iterator over all entries ending with `suffix`, in [`Retrograde`]
+    /// order.
+    ///
+    /// The counterpart to [`SortedString::entries_with_prefix()`]: as the companion
+    /// haystack is sorted by entries read backwards, all such entries are adjacent, and
+    /// found using two binary searches.
+    pub fn entries_with_suffix<U>(&self, suffix: U) -> Entries<'a>
+    where
+        U: AsRef<str>,
+    {
+        let suffix = suffix.as_ref();
+        let ss = &self.0;
+
+        let front =
+            ss.partition_point_tiled(|entry| Retrograde::compare_suffix(entry, suffix).is_lt());
+        let back =
+            ss.partition_point_tiled(|entry| Retrograde::compare_suffix(entry, suffix).is_le());
+
+        ss.entries_between(front, back)
+    }
+
+    /// Returns the byte range spanning all entries ending with `suffix`.
+    ///
+    /// See [`SortedString::prefix_range()`]. [`None`] is returned if no entry ends with
+    /// `suffix`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use b4s::{AsciiChar, SuffixSortedString};
+    ///
+    /// let haystack = "Hoffnung,Zeitung,Bahn";
+    /// let ss = SuffixSortedString::new_checked(haystack, AsciiChar::Comma)?;
+    ///
+    /// assert_eq!(ss.suffix_range("ung"), Some(std::ops::Range { start: 0, end: 16 }));
+    /// assert_eq!(ss.suffix_range("heit"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn suffix_range<U>(&self, suffix: U) -> Option<Range<usize>>
+    where
+        U: AsRef<str>,
+    {
+        self.entries_with_suffix(suffix).span()
+    }
+
+    /// Searches for a needle, see [`SortedString::binary_search()`].
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`SearchError`](crate::SearchError).
+    pub fn binary_search<U>(&self, needle: U) -> SearchResult
+    where
+        U: AsRef<str>,
+    {
+        self.0.binary_search(needle)
+    }
+
+    /// Returns the underlying [`SortedString`], for all other queries.
+    #[must_use]
+    pub const fn as_sorted_string(&self) -> &SortedString<'a, Retrograde> {
+        &self.0
+    }
+}
+
+impl Display for SuffixSortedString<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -1,7 +1,27 @@
 use ascii::AsciiChar;
-use b4s::{SearchError, SearchResult, SortedString};
+#[cfg(feature = "regex")]
+use b4s::RegexQuery;
+use b4s::{
+    Bytewise, CaseInsensitive, EditDistance, Format, Glob, GlobError, InfixIndex, LengthFirst,
+    Natural, Retrograde, Reversed, SearchError, SearchResult, Separator, Side, SortOrder,
+    SortedBytes, SortedString, SuffixSortedString,
+};
+#[cfg(feature = "unicode")]
+use b4s::{Collation, DiacriticInsensitive, NormalizationForm, SortedStringCreationError};
+use itertools::Itertools;
 use rstest::rstest;
-use std::ops::Range;
+use std::{
+    cmp::Ordering,
+    ops::{Bound, Range},
+};
+
+fn not_found(last_probe: Range<usize>, insertion_point: usize, side: Side) -> SearchResult {
+    Err(SearchError {
+        last_probe,
+        insertion_point,
+        side,
+    })
+}
 
 fn base_test(needle: &str, haystack: &str, sep: AsciiChar, expected: SearchResult) {
     let ss = SortedString::new_checked(haystack, sep).unwrap();
@@ -73,9 +93,9 @@ fn test_base_cases(
 }
 
 #[rstest]
-#[case("mn", "abc,mno,yz", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
-#[case("a", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
-#[case("z", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 8, end: 10 })))]
+#[case("mn", "abc,mno,yz", AsciiChar::Comma, not_found(0..3, 4, Side::Right))]
+#[case("a", "abc,def,yz", AsciiChar::Comma, not_found(0..3, 0, Side::Left))]
+#[case("z", "abc,def,yz", AsciiChar::Comma, not_found(8..10, 10, Side::Right))]
 fn test_needle_shorter_than_any_haystack_item(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -86,9 +106,9 @@ fn test_needle_shorter_than_any_haystack_item(
 }
 
 #[rstest]
-#[case("abcd", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
-#[case("xyz", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 4, end: 7 })))]
-#[case("zyz", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 8, end: 10 })))]
+#[case("abcd", "abc,def,yz", AsciiChar::Comma, not_found(0..3, 4, Side::Right))]
+#[case("xyz", "abc,def,yz", AsciiChar::Comma, not_found(8..10, 8, Side::Left))]
+#[case("zyz", "abc,def,yz", AsciiChar::Comma, not_found(8..10, 10, Side::Right))]
 fn test_needle_longer_than_any_haystack_item(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -99,7 +119,7 @@ fn test_needle_longer_than_any_haystack_item(
 }
 
 #[rstest]
-#[case("abc", "a,b,c", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 1 })))]
+#[case("abc", "a,b,c", AsciiChar::Comma, not_found(0..1, 2, Side::Right))]
 fn test_single_character_haystack(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -111,9 +131,9 @@ fn test_single_character_haystack(
 
 #[rstest]
 #[case("a", "a,def,yz", AsciiChar::Comma, Ok(Range { start: 0, end: 1 }))]
-#[case("a", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
+#[case("a", "abc,def,yz", AsciiChar::Comma, not_found(0..3, 0, Side::Left))]
 #[case("z", "abc,def,z", AsciiChar::Comma, Ok(Range { start: 8, end: 9 }))]
-#[case("z", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 8, end: 10 })))]
+#[case("z", "abc,def,yz", AsciiChar::Comma, not_found(8..10, 10, Side::Right))]
 fn test_single_character_needle(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -126,7 +146,7 @@ fn test_single_character_needle(
 #[rstest]
 #[case("a", "a,b,c", AsciiChar::Comma, Ok(Range { start: 0, end: 1 }))]
 #[case("c", "a,b,c", AsciiChar::Comma, Ok(Range { start: 4, end: 5 }))]
-#[case("d", "a,b,c", AsciiChar::Comma, Err(SearchError(Range { start: 4, end: 5 })))]
+#[case("d", "a,b,c", AsciiChar::Comma, not_found(4..5, 5, Side::Right))]
 fn test_single_character_needle_and_haystack(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -138,9 +158,9 @@ fn test_single_character_needle_and_haystack(
 
 #[rstest]
 #[case("aaa", "aaa,def,yz", AsciiChar::Comma, Ok(Range { start: 0, end: 3 }))]
-#[case("aaa", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
+#[case("aaa", "abc,def,yz", AsciiChar::Comma, not_found(0..3, 0, Side::Left))]
 #[case("zzz", "abc,def,zzz", AsciiChar::Comma, Ok(Range { start: 8, end: 11 }))]
-#[case("zzz", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 8, end: 10 })))]
+#[case("zzz", "abc,def,yz", AsciiChar::Comma, not_found(8..10, 10, Side::Right))]
 fn test_repeated_character_needle(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -153,7 +173,7 @@ fn test_repeated_character_needle(
 #[rstest]
 #[case("", ",", AsciiChar::Comma, Ok(Range { start: 0, end: 0 }))]
 #[case("", ",,", AsciiChar::Comma, Ok(Range { start: 1, end: 1 }))]
-#[case("", "abc", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
+#[case("", "abc", AsciiChar::Comma, not_found(0..3, 0, Side::Left))]
 fn test_empty_needle(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -166,7 +186,7 @@ fn test_empty_needle(
 #[rstest]
 #[case("abc", "abc", AsciiChar::Comma, Ok(Range { start: 0, end: 3 }))]
 #[case("abc", "abc,def", AsciiChar::Comma, Ok(Range { start: 0, end: 3 }))]
-#[case("abc", ",", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 0 })))]
+#[case("abc", ",", AsciiChar::Comma, not_found(1..1, 1, Side::Right))]
 #[case("", ",,,,", AsciiChar::Comma, Ok(Range { start: 2, end: 2 }))]
 #[case("abc", ",,,abc", AsciiChar::Comma, Ok(Range { start: 3, end: 6 }))]
 fn test_oddly_shaped_haystack(
@@ -179,8 +199,8 @@ fn test_oddly_shaped_haystack(
 }
 
 #[rstest]
-#[case("nmo", "abc,mno,yz", AsciiChar::Comma, Err(SearchError(Range { start: 4, end: 7 })))]
-#[case("cba", "abc,def,yz", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
+#[case("nmo", "abc,mno,yz", AsciiChar::Comma, not_found(8..10, 8, Side::Left))]
+#[case("cba", "abc,def,yz", AsciiChar::Comma, not_found(0..3, 4, Side::Right))]
 fn test_switched_characters_needle(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -193,7 +213,7 @@ fn test_switched_characters_needle(
 #[rstest]
 #[case("abc", "abc-def-yz", AsciiChar::Minus, Ok(Range { start: 0, end: 3 }))]
 #[case("abc", "abc\0def\0yz", AsciiChar::Null, Ok(Range { start: 0, end: 3 }))]
-#[case("defg", "abc\0def\0yz", AsciiChar::Null, Err(SearchError(Range { start: 4, end: 7 })))]
+#[case("defg", "abc\0def\0yz", AsciiChar::Null, not_found(8..10, 8, Side::Left))]
 fn test_different_separators(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -204,8 +224,8 @@ fn test_different_separators(
 }
 
 #[rstest]
-#[case("abc", "Hund\nKatze\nMaus", AsciiChar::LineFeed, Err(SearchError(Range { start: 11, end: 15 })))]
-#[case("ABC", "Hund\nKatze\nMaus", AsciiChar::LineFeed, Err(SearchError(Range { start: 0, end: 4 })))]
+#[case("abc", "Hund\nKatze\nMaus", AsciiChar::LineFeed, not_found(11..15, 15, Side::Right))]
+#[case("ABC", "Hund\nKatze\nMaus", AsciiChar::LineFeed, not_found(0..4, 0, Side::Left))]
 #[case("Hund", "Hund\nKatze\nMaus", AsciiChar::LineFeed, Ok(Range { start: 0, end: 4 }))]
 #[case("Katze", "Hund\nKatze\nMaus", AsciiChar::LineFeed, Ok(Range { start: 5, end: 10 }))]
 #[case("Maus", "Hund\nKatze\nMaus", AsciiChar::LineFeed, Ok(Range { start: 11, end: 15 }))]
@@ -219,7 +239,7 @@ fn test_real_world_examples(
 }
 
 #[rstest]
-#[case("Hündin", "Hund\nKatze\nMaus", AsciiChar::LineFeed, Err(SearchError(Range { start: 5, end: 10 })))]
+#[case("Hündin", "Hund\nKatze\nMaus", AsciiChar::LineFeed, not_found(0..4, 5, Side::Right))]
 #[case("Hündin", "Hündin\nKatze\nMaus", AsciiChar::LineFeed, Ok(Range { start: 0, end: 7 }))]
 #[case(
     "Mäuschen",
@@ -259,7 +279,7 @@ fn test_real_world_examples_with_common_prefixes(
 }
 
 #[rstest]
-#[case("abc,def", "abc,def,ghi", AsciiChar::Comma, Err(SearchError(Range { start: 0, end: 3 })))]
+#[case("abc,def", "abc,def,ghi", AsciiChar::Comma, not_found(0..3, 4, Side::Right))]
 fn test_needle_contains_separator(
     #[case] needle: &str,
     #[case] haystack: &str,
@@ -271,8 +291,8 @@ fn test_needle_contains_separator(
 
 #[rstest]
 #[case("", ",a", AsciiChar::Comma, Ok(Range { start: 0, end: 0 }))]
-#[should_panic]
-fn test_buggy_cases_to_be_fixed(
+#[case("", ",,a", AsciiChar::Comma, Ok(Range { start: 1, end: 1 }))]
+fn test_leading_empty_entries(
     #[case] needle: &str,
     #[case] haystack: &str,
     #[case] sep: AsciiChar,
@@ -281,6 +301,36 @@ fn test_buggy_cases_to_be_fixed(
     base_test(needle, haystack, sep, expected)
 }
 
+#[rstest]
+#[case("a", "b,c,d", AsciiChar::Comma, "a,b,c,d")]
+#[case("bb", "a,c", AsciiChar::Comma, "a,bb,c")]
+#[case("z", "a,c", AsciiChar::Comma, "a,c,z")]
+#[case("b", "a,c,c,d", AsciiChar::Comma, "a,b,c,c,d")]
+#[case("a", ",", AsciiChar::Comma, ",,a")]
+#[case("Äpfel", "Apfel\nBaum", AsciiChar::LineFeed, "Apfel\nBaum\nÄpfel")]
+fn test_insertion_point_keeps_haystack_sorted(
+    #[case] needle: &str,
+    #[case] haystack: &str,
+    #[case] sep: AsciiChar,
+    #[case] expected: &str,
+) {
+    let ss = SortedString::new_checked(haystack, sep).unwrap();
+    let err = ss.binary_search(needle).unwrap_err();
+
+    let mut owned = haystack.to_string();
+    if err.insertion_point == haystack.len() {
+        assert_eq!(err.side, Side::Right);
+        owned.push(sep.as_char());
+        owned.push_str(needle);
+    } else {
+        owned.insert(err.insertion_point, sep.as_char());
+        owned.insert_str(err.insertion_point, needle);
+    }
+
+    assert_eq!(owned, expected);
+    assert!(SortedString::new_checked(&owned, sep).is_ok());
+}
+
 #[rstest]
 #[case("c,b,a", AsciiChar::Comma)]
 #[case("a,", AsciiChar::Comma)]
@@ -301,3 +351,1817 @@ fn test_empty_haystack(#[case] haystack: &str, #[case] sep: AsciiChar) {
     let ss = SortedString::new_checked(haystack, sep);
     assert_eq!(ss, Err(b4s::SortedStringCreationError::EmptyHaystack));
 }
+
+#[rstest]
+#[case("", "abc,abd,b", AsciiChar::Comma, vec!["abc", "abd", "b"], Some(Range { start: 0, end: 9 }))]
+#[case("a", "abc,abd,b", AsciiChar::Comma, vec!["abc", "abd"], Some(Range { start: 0, end: 7 }))]
+#[case("ab", "a,abc,abd,b", AsciiChar::Comma, vec!["abc", "abd"], Some(Range { start: 2, end: 9 }))]
+#[case("abd", "a,abc,abd,b", AsciiChar::Comma, vec!["abd"], Some(Range { start: 6, end: 9 }))]
+#[case("b", "a,abc,abd,b", AsciiChar::Comma, vec!["b"], Some(Range { start: 10, end: 11 }))]
+#[case("abcd", "a,abc,abd,b", AsciiChar::Comma, vec![], None)]
+#[case("0", "a,abc,abd,b", AsciiChar::Comma, vec![], None)]
+#[case("c", "a,abc,abd,b", AsciiChar::Comma, vec![], None)]
+#[case("", ",", AsciiChar::Comma, vec!["", ""], Some(Range { start: 0, end: 1 }))]
+#[case("", "", AsciiChar::Comma, vec![""], Some(Range { start: 0, end: 0 }))]
+#[case("a", ",a,ab", AsciiChar::Comma, vec!["a", "ab"], Some(Range { start: 1, end: 5 }))]
+#[case("Ä", "Apfel\nÄpfel\nÄrger\nÖfen", AsciiChar::LineFeed, vec!["Äpfel", "Ärger"], Some(Range { start: 6, end: 19 }))]
+fn test_prefix(
+    #[case] prefix: &str,
+    #[case] haystack: &str,
+    #[case] sep: AsciiChar,
+    #[case] expected_entries: Vec<&str>,
+    #[case] expected_range: Option<Range<usize>>,
+) {
+    let ss = SortedString::new_unchecked(haystack, sep);
+
+    assert_eq!(
+        ss.entries_with_prefix(prefix).collect::<Vec<_>>(),
+        expected_entries
+    );
+    assert_eq!(ss.prefix_range(prefix), expected_range);
+
+    let mut reversed = ss.entries_with_prefix(prefix).rev().collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(reversed, expected_entries);
+}
+
+#[rstest]
+#[case((Bound::Included("b"), Bound::Excluded("d")), vec!["b", "bb", "c"])]
+#[case((Bound::Included("b"), Bound::Included("d")), vec!["b", "bb", "c", "d"])]
+#[case((Bound::Excluded("b"), Bound::Included("d")), vec!["bb", "c", "d"])]
+#[case((Bound::Excluded("b"), Bound::Excluded("d")), vec!["bb", "c"])]
+#[case((Bound::Unbounded, Bound::Excluded("bb")), vec!["a", "b"])]
+#[case((Bound::Included("ba"), Bound::Unbounded), vec!["bb", "c", "d", "e"])]
+#[case((Bound::Unbounded, Bound::Unbounded), vec!["a", "b", "bb", "c", "d", "e"])]
+#[case((Bound::Included("0"), Bound::Included("1")), vec![])]
+#[case((Bound::Included("x"), Bound::Included("z")), vec![])]
+#[case((Bound::Included("c"), Bound::Included("b")), vec![])]
+#[case((Bound::Excluded("c"), Bound::Excluded("c")), vec![])]
+#[case((Bound::Included("c"), Bound::Included("c")), vec!["c"])]
+fn test_range(#[case] bounds: (Bound<&str>, Bound<&str>), #[case] expected: Vec<&str>) {
+    let haystack = "a,b,bb,c,d,e";
+    let ss = SortedString::new_checked(haystack, AsciiChar::Comma).unwrap();
+
+    let entries = ss.range::<str, _>(bounds);
+    match entries.span() {
+        Some(span) => assert_eq!(haystack[span].split(',').collect::<Vec<_>>(), expected),
+        None => assert!(expected.is_empty()),
+    }
+    assert_eq!(entries.clone().collect::<Vec<_>>(), expected);
+
+    let mut reversed = entries.rev().collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(reversed, expected);
+}
+
+#[test]
+fn test_range_syntax() {
+    let ss = SortedString::new_checked("Aachen\nAmpel\nAngel\nApfel", AsciiChar::LineFeed).unwrap();
+
+    assert_eq!(
+        ss.range("Am".."Ap").collect::<Vec<_>>(),
+        vec!["Ampel", "Angel"]
+    );
+    assert_eq!(ss.range("Ampel"..="Apfel").count(), 3);
+    assert_eq!(
+        ss.range("Angel"..).collect::<Vec<_>>(),
+        vec!["Angel", "Apfel"]
+    );
+    assert_eq!(ss.range(.."Ampel").collect::<Vec<_>>(), vec!["Aachen"]);
+    assert_eq!(ss.range::<str, _>(..).count(), 4);
+
+    let owned = String::from("Angel");
+    assert_eq!(ss.range(owned..).next(), Some("Angel"));
+}
+
+#[rstest]
+#[case("a", "a,b,b,c", AsciiChar::Comma, 0, 2)]
+#[case("b", "a,b,b,c", AsciiChar::Comma, 2, 6)]
+#[case("ba", "a,b,b,c", AsciiChar::Comma, 6, 6)]
+#[case("c", "a,b,b,c", AsciiChar::Comma, 6, 7)]
+#[case("0", "a,b,b,c", AsciiChar::Comma, 0, 0)]
+#[case("d", "a,b,b,c", AsciiChar::Comma, 7, 7)]
+#[case("", ",a", AsciiChar::Comma, 0, 1)]
+#[case("", ",,a", AsciiChar::Comma, 0, 2)]
+#[case("Äpfel", "Apfel\nÄpfel\nÖfen", AsciiChar::LineFeed, 6, 13)]
+fn test_lower_and_upper_bound(
+    #[case] needle: &str,
+    #[case] haystack: &str,
+    #[case] sep: AsciiChar,
+    #[case] lower: usize,
+    #[case] upper: usize,
+) {
+    let ss = SortedString::new_checked(haystack, sep).unwrap();
+
+    assert_eq!(ss.lower_bound(needle), lower);
+    assert_eq!(ss.upper_bound(needle), upper);
+}
+
+#[rstest]
+#[case(0, 0)]
+#[case(1, 0)]
+#[case(2, 2)]
+#[case(3, 5)]
+#[case(4, 9)]
+#[case(5, 13)]
+fn test_partition_point(#[case] max_len: usize, #[case] expected: usize) {
+    let haystack = "a,bb,ccc,dddd";
+    let ss = SortedString::new_checked(haystack, AsciiChar::Comma).unwrap();
+
+    assert_eq!(ss.partition_point(|entry| entry.len() < max_len), expected);
+}
+
+#[rstest]
+#[case("0", "b,d,d,f", AsciiChar::Comma, None, Some((Range { start: 0, end: 1 }, "b")))]
+#[case("b", "b,d,d,f", AsciiChar::Comma, None, Some((Range { start: 2, end: 3 }, "d")))]
+#[case("c", "b,d,d,f", AsciiChar::Comma, Some((Range { start: 0, end: 1 }, "b")), Some((Range { start: 2, end: 3 }, "d")))]
+#[case("d", "b,d,d,f", AsciiChar::Comma, Some((Range { start: 0, end: 1 }, "b")), Some((Range { start: 6, end: 7 }, "f")))]
+#[case("e", "b,d,d,f", AsciiChar::Comma, Some((Range { start: 4, end: 5 }, "d")), Some((Range { start: 6, end: 7 }, "f")))]
+#[case("f", "b,d,d,f", AsciiChar::Comma, Some((Range { start: 4, end: 5 }, "d")), None)]
+#[case("g", "b,d,d,f", AsciiChar::Comma, Some((Range { start: 6, end: 7 }, "f")), None)]
+#[case("a", ",", AsciiChar::Comma, Some((Range { start: 1, end: 1 }, "")), None)]
+#[case("", ",a", AsciiChar::Comma, None, Some((Range { start: 1, end: 2 }, "a")))]
+#[case("Bäume", "Apfel\nBaum\nZaun", AsciiChar::LineFeed, Some((Range { start: 6, end: 10 }, "Baum")), Some((Range { start: 11, end: 15 }, "Zaun")))]
+fn test_predecessor_and_successor(
+    #[case] needle: &str,
+    #[case] haystack: &str,
+    #[case] sep: AsciiChar,
+    #[case] predecessor: Option<(Range<usize>, &str)>,
+    #[case] successor: Option<(Range<usize>, &str)>,
+) {
+    let ss = SortedString::new_checked(haystack, sep).unwrap();
+
+    assert_eq!(ss.predecessor(needle), predecessor);
+    assert_eq!(ss.successor(needle), successor);
+}
+
+#[rstest]
+#[case("abc", "abc,def,yz", AsciiChar::Comma, Some(("abc", Range { start: 0, end: 3 })))]
+#[case("yz", "abc,def,yz", AsciiChar::Comma, Some(("yz", Range { start: 8, end: 10 })))]
+#[case("xyz", "abc,def,yz", AsciiChar::Comma, None)]
+#[case("", ",a", AsciiChar::Comma, Some(("", Range { start: 0, end: 0 })))]
+#[case("Mäuschen", "Hündin\nKatze\nMäuschen", AsciiChar::LineFeed, Some(("Mäuschen", Range { start: 14, end: 23 })))]
+fn test_search_match(
+    #[case] needle: &str,
+    #[case] haystack: &str,
+    #[case] sep: AsciiChar,
+    #[case] expected: Option<(&str, Range<usize>)>,
+) {
+    let ss = SortedString::new_checked(haystack, sep).unwrap();
+    let result = ss.search(needle);
+
+    match expected {
+        Some((entry, range)) => {
+            let m = result.unwrap();
+            assert_eq!(m.entry, entry);
+            assert_eq!(&haystack[m.range.clone()], entry);
+            assert_eq!(m.range, range);
+        }
+        None => assert_eq!(result.unwrap_err(), ss.binary_search(needle).unwrap_err()),
+    }
+}
+
+#[test]
+fn test_match_outlives_sorted_string() {
+    let haystack = String::from("a,b,c");
+
+    let m = {
+        let ss = SortedString::new_checked(&haystack, AsciiChar::Comma).unwrap();
+        ss.search("b").unwrap()
+    };
+
+    assert_eq!(m.to_string(), "b");
+    assert_eq!(m.as_ref(), "b");
+}
+
+#[rstest]
+#[case("abc,def,yz", AsciiChar::Comma)]
+#[case("a", AsciiChar::Comma)]
+#[case("", AsciiChar::Comma)]
+#[case(",", AsciiChar::Comma)]
+#[case(",,a", AsciiChar::Comma)]
+#[case("Hündin\nKatze\nMäuschen", AsciiChar::LineFeed)]
+fn test_iter_matches_split(#[case] haystack: &str, #[case] sep: AsciiChar) {
+    let ss = SortedString::new_unchecked(haystack, sep);
+    let expected = haystack.split(sep.as_char()).collect::<Vec<_>>();
+
+    assert_eq!(ss.iter().collect::<Vec<_>>(), expected);
+    assert_eq!((&ss).into_iter().count(), expected.len());
+
+    let mut reversed = ss.iter().rev().collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(reversed, expected);
+
+    for (range, entry) in ss.iter_with_ranges() {
+        assert_eq!(&haystack[range], entry);
+    }
+    assert_eq!(
+        ss.iter_with_ranges()
+            .rev()
+            .map(|(_, entry)| entry)
+            .collect::<Vec<_>>(),
+        ss.iter().rev().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_iter_from_both_ends() {
+    let ss = SortedString::new_checked("a,b,c,d", AsciiChar::Comma).unwrap();
+    let mut iter = ss.iter();
+
+    assert_eq!(iter.next(), Some("a"));
+    assert_eq!(iter.next_back(), Some("d"));
+    assert_eq!(iter.next(), Some("b"));
+    assert_eq!(iter.next_back(), Some("c"));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[rstest]
+#[case("abc,def,ghi,jkl,mno,pqr,stu,vwx,yz", AsciiChar::Comma)]
+#[case("a", AsciiChar::Comma)]
+#[case(",", AsciiChar::Comma)]
+#[case(",a,aa,aaa,b,bb", AsciiChar::Comma)]
+#[case("a,a,a,b,b,c", AsciiChar::Comma)]
+#[case("Hündin\nKatze\nMäuschen", AsciiChar::LineFeed)]
+fn test_binary_search_many_agrees_with_binary_search(
+    #[case] haystack: &str,
+    #[case] sep: AsciiChar,
+) {
+    let ss = SortedString::new_checked(haystack, sep).unwrap();
+
+    let mut needles = vec![
+        "",
+        "0",
+        "a",
+        "aa",
+        "ab",
+        "abc",
+        "abcd",
+        "b",
+        "c",
+        "d",
+        "def",
+        "ghi",
+        "Hund",
+        "Hündin",
+        "Katze",
+        "Mäuschen",
+        "yz",
+        "z",
+        "zzz",
+        "ä",
+    ];
+    needles.extend(ss.iter());
+
+    let mut sorted_needles = needles.clone();
+    sorted_needles.sort_unstable();
+    let mut reversed_needles = sorted_needles.clone();
+    reversed_needles.reverse();
+
+    for needles in [needles, sorted_needles, reversed_needles] {
+        let results = ss.binary_search_many(&needles);
+        assert_eq!(results.len(), needles.len());
+
+        for (needle, result) in needles.iter().zip(results) {
+            match (result, ss.binary_search(needle)) {
+                (Ok(range), Ok(_)) => assert_eq!(&haystack[range], *needle),
+                (Err(batch), Err(single)) => {
+                    assert_eq!(batch.insertion_point, single.insertion_point);
+                }
+                (batch, single) => panic!("{needle:?}: {batch:?} vs. {single:?}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_binary_search_many_empty_input() {
+    let ss = SortedString::new_checked("a,b", AsciiChar::Comma).unwrap();
+
+    assert!(ss.binary_search_many(Vec::<String>::new()).is_empty());
+}
+
+#[rstest]
+#[case("apple", Ok(Range { start: 0, end: 9 }))]
+#[case("banana", Ok(Range { start: 10, end: 23 }))]
+#[case("cherry", Ok(Range { start: 24, end: 34 }))]
+#[case("aardvark", not_found(0..9, 0, Side::Left))]
+#[case("blueberry", not_found(24..34, 24, Side::Left))]
+#[case("date", not_found(24..34, 34, Side::Right))]
+fn test_binary_search_by_key(#[case] key: &str, #[case] expected: SearchResult) {
+    let haystack = "apple:red\nbanana:yellow\ncherry:red";
+    let ss = SortedString::new_checked(haystack, AsciiChar::LineFeed).unwrap();
+
+    let extract = |entry: &'static str| entry.split(':').next().unwrap();
+    assert_eq!(ss.binary_search_by_key(&key, extract), expected);
+}
+
+#[rstest]
+#[case("a", Ok(Range { start: 4, end: 5 }))]
+#[case("b", Ok(Range { start: 2, end: 3 }))]
+#[case("c", Ok(Range { start: 0, end: 1 }))]
+#[case("bb", not_found(0..1, 2, Side::Right))]
+#[case("d", not_found(0..1, 0, Side::Left))]
+fn test_binary_search_by_reverse_order(#[case] needle: &str, #[case] expected: SearchResult) {
+    let ss = SortedString::new_unchecked("c,b,a", AsciiChar::Comma);
+
+    assert_eq!(ss.binary_search_by(|entry| needle.cmp(entry)), expected);
+}
+
+#[rstest]
+#[case("c,b,a", Reversed(Bytewise), true)]
+#[case("a,b,c", Reversed(Bytewise), false)]
+#[case("c,bb,b,a", Reversed(Bytewise), true)]
+#[case("Hund\nKatze\nMaus", Reversed(Bytewise), false)]
+fn test_reversed_validation(#[case] haystack: &str, #[case] order: Reversed, #[case] sorted: bool) {
+    let sep = if haystack.contains(',') {
+        AsciiChar::Comma
+    } else {
+        AsciiChar::LineFeed
+    };
+    let ss = SortedString::new_checked_with_order(haystack, sep, order);
+
+    assert_eq!(ss.is_ok(), sorted);
+}
+
+#[rstest]
+#[case("ab", Ok(8))]
+#[case("a", Ok(14))]
+#[case("c", Err(0))]
+#[case("aab", Err(11))]
+#[case("", Err(15))]
+fn test_reversed_search(#[case] needle: &str, #[case] expected: Result<usize, usize>) {
+    let haystack = "b,abc,aa,ab,a,b";
+    let sorted = SortedString::sort_with_order(haystack, AsciiChar::Comma, &Reversed(Bytewise));
+    assert_eq!(sorted, "b,b,abc,ab,aa,a");
+
+    let ss = SortedString::new_checked_with_order(&sorted, AsciiChar::Comma, Reversed(Bytewise))
+        .unwrap();
+    let result = ss
+        .binary_search(needle)
+        .map(|range| range.start)
+        .map_err(|err| err.insertion_point);
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_reversed_queries() {
+    let ss =
+        SortedString::new_checked_with_order("c,bb,ba,b,a", AsciiChar::Comma, Reversed(Bytewise))
+            .unwrap();
+
+    assert_eq!(
+        ss.entries_with_prefix("b").collect::<Vec<_>>(),
+        vec!["bb", "ba", "b"]
+    );
+    assert_eq!(
+        ss.range("bb"..="b").collect::<Vec<_>>(),
+        vec!["bb", "ba", "b"]
+    );
+    assert_eq!(ss.predecessor("bab").map(|(_, entry)| entry), Some("bb"));
+    assert_eq!(ss.successor("bab").map(|(_, entry)| entry), Some("ba"));
+    assert_eq!(ss.lower_bound("ba"), 5);
+    assert_eq!(ss.upper_bound("ba"), 8);
+}
+
+#[rstest]
+#[case("b,z,ab,ba,aaa", true)]
+#[case("b,ab,z", false)]
+#[case("ä,ab", true)]
+fn test_length_first(#[case] haystack: &str, #[case] sorted: bool) {
+    let sep = AsciiChar::Comma;
+    let ss = SortedString::new_checked_with_order(haystack, sep, LengthFirst);
+    assert_eq!(ss.is_ok(), sorted);
+
+    let resorted = SortedString::sort_with_order(haystack, sep, &LengthFirst);
+    let ss = SortedString::new_checked_with_order(&resorted, sep, LengthFirst).unwrap();
+    for entry in haystack.split(',') {
+        let range = ss.binary_search(entry).unwrap();
+        assert_eq!(&resorted[range], entry);
+    }
+}
+
+#[test]
+fn test_closure_order() {
+    let by_len = |a: &str, b: &str| a.len().cmp(&b.len());
+    let ss = SortedString::new_checked_with_order("x,yy,zzz", AsciiChar::Comma, by_len).unwrap();
+
+    assert_eq!(ss.binary_search("aa"), Ok(Range { start: 2, end: 4 }));
+    assert_eq!(ss.binary_search("aaaa").unwrap_err().insertion_point, 8);
+}
+
+#[rstest]
+#[case("apfel\nBaum\nZaun", true)]
+#[case("Apfel\napfel\nAPFEL", true)]
+#[case("Baum\napfel", false)]
+#[case("Masse\nMaße\nMAẞE\nmassen", true)]
+#[case("Maßen\nMasse", false)]
+fn test_case_insensitive_validation(#[case] haystack: &str, #[case] sorted: bool) {
+    let ss = SortedString::new_checked_with_order(haystack, AsciiChar::LineFeed, CaseInsensitive);
+
+    assert_eq!(ss.is_ok(), sorted);
+}
+
+#[rstest]
+#[case("ΟΔΟΣ", "οδος", Ordering::Equal)]
+#[case("ſeit", "SEIT", Ordering::Equal)]
+#[case("ﬁsch", "Fisch", Ordering::Equal)]
+#[case("ﬂuss", "FLUSS", Ordering::Equal)]
+#[case("Schiﬀ", "SCHIFF", Ordering::Equal)]
+#[case("Maß", "MASS", Ordering::Equal)]
+// Not among the selected folds.
+#[case("ϐ", "β", Ordering::Greater)]
+fn test_case_insensitive_selected_folds(
+    #[case] a: &str,
+    #[case] b: &str,
+    #[case] expected: Ordering,
+) {
+    assert_eq!(CaseInsensitive.compare(a, b), expected);
+    assert_eq!(CaseInsensitive.compare(b, a), expected.reverse());
+}
+
+#[rstest]
+#[case("apfel", Some("Apfel"))]
+#[case("APFEL", Some("Apfel"))]
+#[case("bAuM", Some("baum"))]
+#[case("STRASSE", Some("Straße"))]
+#[case("straße", Some("Straße"))]
+#[case("ÄPFEL", Some("äpfel"))]
+#[case("Zaun", None)]
+#[case("Apfe", None)]
+fn test_case_insensitive_search(#[case] needle: &str, #[case] expected: Option<&str>) {
+    let haystack = SortedString::sort_with_order(
+        "Straße\näpfel\nbaum\nApfel\nMaus",
+        AsciiChar::LineFeed,
+        &CaseInsensitive,
+    );
+    let ss = SortedString::new_checked_with_order(&haystack, AsciiChar::LineFeed, CaseInsensitive)
+        .unwrap();
+
+    assert_eq!(ss.search(needle).ok().map(|m| m.entry), expected);
+}
+
+#[rstest]
+#[case("a", vec!["apfel", "Ampel"])]
+#[case("AP", vec!["apfel"])]
+#[case("M", vec!["Maße", "MASSEN"])]
+#[case("mass", vec!["Maße", "MASSEN"])]
+#[case("maßen", vec!["MASSEN"])]
+#[case("x", vec![])]
+fn test_case_insensitive_prefix(#[case] prefix: &str, #[case] expected: Vec<&str>) {
+    let haystack = "Ampel\napfel\nMaße\nMASSEN";
+    let haystack = SortedString::sort_with_order(haystack, AsciiChar::LineFeed, &CaseInsensitive);
+    let ss = SortedString::new_checked_with_order(&haystack, AsciiChar::LineFeed, CaseInsensitive)
+        .unwrap();
+
+    let mut entries = ss.entries_with_prefix(prefix).collect::<Vec<_>>();
+    let mut expected = expected;
+    entries.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(entries, expected);
+}
+
+#[rstest]
+#[case("b", vec!["b", "b"])]
+#[case("a", vec!["a"])]
+#[case("bb", vec![])]
+#[case("", vec![])]
+fn test_search_all(#[case] needle: &str, #[case] expected: Vec<&str>) {
+    let ss = SortedString::new_checked("a,b,b,c", AsciiChar::Comma).unwrap();
+
+    assert_eq!(ss.search_all(needle).collect::<Vec<_>>(), expected);
+}
+
+#[rstest]
+#[case("apfel", vec!["apfel", "Apfel", "APFEL"])]
+#[case("AMPEL", vec!["Ampel"])]
+#[case("Angel", vec![])]
+fn test_search_all_case_insensitive(#[case] needle: &str, #[case] expected: Vec<&str>) {
+    let haystack = "Ampel\napfel\nApfel\nAPFEL\nBaum";
+    let ss = SortedString::new_checked_with_order(haystack, AsciiChar::LineFeed, CaseInsensitive)
+        .unwrap();
+
+    assert_eq!(ss.search_all(needle).collect::<Vec<_>>(), expected);
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case("Apfel\nÄpfel\nBaum", true)]
+#[case("Äpfel\nApfel\nBaum", true)]
+#[case("Ärger\nApfel", false)]
+#[case("cafe\ncafé\ncafes", true)]
+#[case("cafés\ncafe", false)]
+#[case("Strasse\nStraße\nStrassen", true)]
+fn test_diacritic_insensitive_validation(#[case] haystack: &str, #[case] sorted: bool) {
+    let ss = SortedString::new_checked_with_order(
+        haystack,
+        AsciiChar::LineFeed,
+        DiacriticInsensitive::new(),
+    );
+
+    assert_eq!(ss.is_ok(), sorted);
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case("Apfel", vec!["Äpfel", "Apfel"])]
+#[case("Äpfel", vec!["Äpfel", "Apfel"])]
+#[case("cafe", vec!["café", "cafe\u{301}", "cafe"])]
+#[case("cafe\u{301}", vec!["café", "cafe\u{301}", "cafe"])]
+#[case("Strasse", vec!["Straße"])]
+#[case("Smorrebrod", vec!["Smørrebrød"])]
+#[case("apfel", vec![])]
+fn test_diacritic_insensitive_search_all(#[case] needle: &str, #[case] expected: Vec<&str>) {
+    let order = DiacriticInsensitive::new();
+    // Unsorted, with `café` both composed and decomposed.
+    let unsorted = "Straße\ncafé\nSmørrebrød\nÄpfel\ncafe\u{301}\nApfel\ncafe";
+    let haystack = SortedString::sort_with_order(unsorted, AsciiChar::LineFeed, &order);
+    let ss = SortedString::new_checked_with_order(&haystack, AsciiChar::LineFeed, order).unwrap();
+
+    let entries = ss.search_all(needle).collect::<Vec<_>>();
+    assert_eq!(entries, expected);
+
+    if expected.is_empty() {
+        assert!(ss.binary_search(needle).is_err());
+    } else {
+        let m = ss.search(needle).unwrap();
+        assert!(expected.contains(&m.entry));
+    }
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case("A", vec!["Ampel", "Äpfel"])]
+#[case("Äp", vec!["Äpfel"])]
+#[case("Stras", vec!["Straße"])]
+#[case("Straß", vec!["Straße"])]
+#[case("Straßen", vec![])]
+fn test_diacritic_insensitive_prefix(#[case] prefix: &str, #[case] expected: Vec<&str>) {
+    let order = DiacriticInsensitive::new();
+    let haystack = "Ampel\nÄpfel\nStraße";
+    let ss = SortedString::new_checked_with_order(haystack, AsciiChar::LineFeed, order).unwrap();
+
+    assert_eq!(ss.entries_with_prefix(prefix).collect::<Vec<_>>(), expected);
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case("Apfel\nBaum", NormalizationForm::Nfc, true)]
+#[case("Apfel\nBaum", NormalizationForm::Nfkd, true)]
+#[case("Baum\nBäume", NormalizationForm::Nfc, true)]
+#[case("Baum\nBäume", NormalizationForm::Nfd, false)]
+#[case("Baum\nBa\u{308}ume", NormalizationForm::Nfc, false)]
+#[case("Baum\nBa\u{308}ume", NormalizationForm::Nfd, true)]
+#[case("ﬁsh\nfish", NormalizationForm::Nfc, true)]
+#[case("ﬁsh\nfish", NormalizationForm::Nfkc, false)]
+fn test_ensure_normalized(
+    #[case] haystack: &str,
+    #[case] form: NormalizationForm,
+    #[case] normalized: bool,
+) {
+    let ss = SortedString::new_unchecked(haystack, AsciiChar::LineFeed);
+
+    let expected = if normalized {
+        Ok(ss.clone())
+    } else {
+        Err(SortedStringCreationError::NotNormalized(form))
+    };
+    assert_eq!(ss.ensure_normalized(form), expected);
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case("Bäume", Ok(5..11))]
+#[case("Ba\u{308}ume", Ok(5..11))]
+#[case("Baum", Ok(0..4))]
+#[case("Ba\u{308}umen", not_found(5..11, 11, Side::Right))]
+fn test_binary_search_normalized(#[case] needle: &str, #[case] expected: SearchResult) {
+    let ss = SortedString::new_checked("Baum\nBäume", AsciiChar::LineFeed).unwrap();
+
+    assert_eq!(
+        ss.binary_search_normalized(needle, NormalizationForm::Nfc),
+        expected
+    );
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case(NormalizationForm::Nfc)]
+#[case(NormalizationForm::Nfd)]
+fn test_binary_search_normalized_long_needle(#[case] form: NormalizationForm) {
+    // Far exceeding any inline buffer once normalized.
+    let entry = "äöü".repeat(50);
+    let decomposed = "a\u{308}o\u{308}u\u{308}".repeat(50);
+    let haystack = match form {
+        NormalizationForm::Nfd => format!("a\n{decomposed}"),
+        _ => format!("a\n{entry}"),
+    };
+    let ss = SortedString::new_checked(&haystack, AsciiChar::LineFeed)
+        .unwrap()
+        .ensure_normalized(form)
+        .unwrap();
+
+    for needle in [&entry, &decomposed] {
+        assert_eq!(
+            ss.binary_search_normalized(needle, form),
+            Ok(2..haystack.len())
+        );
+    }
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case(Collation::ROOT, "b\nB\na\nA", "a\nA\nb\nB")]
+#[case(Collation::ROOT, "z\n10\n-x\n2\nÉ\ne", "-x\n10\n2\ne\nÉ\nz")]
+#[case(
+    Collation::GERMAN,
+    "Zaun\nÄpfel\nApfel\nAffe",
+    "Affe\nApfel\nÄpfel\nZaun"
+)]
+#[case(
+    Collation::GERMAN,
+    "Strasse\nStraße\nStrassen\nStrand",
+    "Strand\nStrasse\nStraße\nStrassen"
+)]
+#[case(
+    Collation::GERMAN,
+    "Müller\nMuller\nMueller",
+    "Mueller\nMuller\nMüller"
+)]
+#[case(
+    Collation::GERMAN_PHONEBOOK,
+    "Müller\nMuller\nMueller",
+    "Mueller\nMüller\nMuller"
+)]
+#[case(
+    Collation::SWEDISH,
+    "Öl\nZebra\nÄpple\nApa\nÅsa",
+    "Apa\nZebra\nÅsa\nÄpple\nÖl"
+)]
+#[case(Collation::SWEDISH, "Ørn\nÖst\nOla", "Ola\nØrn\nÖst")]
+#[case(Collation::DANISH, "Åse\nØl\nÆble\nZebra", "Zebra\nÆble\nØl\nÅse")]
+#[case(Collation::SPANISH, "ñu\nnube\noso", "nube\nñu\noso")]
+#[case(Collation::ROOT, "oso\nnube\nñu", "ñu\nnube\noso")]
+#[case(Collation::SWEDISH, "A\u{30a}sa\nZebra", "Zebra\nA\u{30a}sa")]
+fn test_collation_sort(
+    #[case] collation: Collation,
+    #[case] unsorted: &str,
+    #[case] expected: &str,
+) {
+    let sep = AsciiChar::LineFeed;
+    let sorted = SortedString::sort_with_order(unsorted, sep, &collation);
+    assert_eq!(sorted, expected);
+
+    let ss = SortedString::new_checked_with_order(&sorted, sep, collation).unwrap();
+    for entry in unsorted.split('\n') {
+        assert_eq!(ss.search(entry).unwrap().entry, entry);
+    }
+}
+
+#[cfg(feature = "unicode")]
+#[rstest]
+#[case(Collation::SWEDISH, "Apa\nZebra\nÅsa", true)]
+#[case(Collation::GERMAN, "Apa\nZebra\nÅsa", false)]
+#[case(Collation::GERMAN, "Apa\nÅsa\nZebra", true)]
+#[case(Collation::GERMAN, "apfel\nApfel", true)]
+#[case(Collation::GERMAN, "Apfel\napfel", false)]
+fn test_collation_validation(
+    #[case] collation: Collation,
+    #[case] haystack: &str,
+    #[case] sorted: bool,
+) {
+    let ss = SortedString::new_checked_with_order(haystack, AsciiChar::LineFeed, collation);
+
+    assert_eq!(ss.is_ok(), sorted);
+}
+
+#[rstest]
+#[case("file10,file2,file1", "file1,file2,file10")]
+#[case("v1.10.0,v1.9.0,v1.9.10,v1.9.2", "v1.9.0,v1.9.2,v1.9.10,v1.10.0")]
+#[case("a10b2,a10b10,a2b20", "a2b20,a10b2,a10b10")]
+#[case("7,007,07,6", "6,007,07,7")]
+#[case("x,10,9,", ",9,10,x")]
+#[case("a1,a,a-1,a01a", "a,a-1,a1,a01a")]
+#[case(
+    "99999999999999999999999,100000000000000000000000",
+    "99999999999999999999999,100000000000000000000000"
+)]
+fn test_natural_sort(#[case] unsorted: &str, #[case] expected: &str) {
+    let sorted = SortedString::sort_with_order(unsorted, AsciiChar::Comma, &Natural);
+    assert_eq!(sorted, expected);
+
+    let ss = SortedString::new_checked_with_order(&sorted, AsciiChar::Comma, Natural).unwrap();
+    for entry in unsorted.split(',') {
+        assert_eq!(ss.search(entry).unwrap().entry, entry);
+    }
+}
+
+#[rstest]
+#[case("file1,file2,file10", true)]
+#[case("file1,file10,file2", false)]
+#[case("img9.png,img10.png", true)]
+#[case("img10.png,img9.png", false)]
+fn test_natural_validation(#[case] haystack: &str, #[case] sorted: bool) {
+    let ss = SortedString::new_checked_with_order(haystack, AsciiChar::Comma, Natural);
+
+    assert_eq!(ss.is_ok(), sorted);
+}
+
+#[rstest]
+#[case("file3", not_found(12..18, 12, Side::Left))]
+#[case("file11", not_found(12..18, 18, Side::Right))]
+// Equal numerically, but sorts before by bytes
+#[case("file010", not_found(12..18, 12, Side::Left))]
+fn test_natural_search_missing(#[case] needle: &str, #[case] expected: SearchResult) {
+    let ss = SortedString::new_checked_with_order("file1,file2,file10", AsciiChar::Comma, Natural)
+        .unwrap();
+
+    assert_eq!(ss.binary_search(needle), expected);
+}
+
+/// Unoptimized reference implementation, see [`SortedString::fuzzy_search()`].
+fn edit_distance(a: &str, b: &str, metric: EditDistance) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + substitution);
+
+            if metric == EditDistance::Damerau
+                && i > 1
+                && j > 1
+                && a[i - 1] == b[j - 2]
+                && a[i - 2] == b[j - 1]
+            {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+#[rstest]
+fn test_fuzzy_search_agrees_with_reference(
+    #[values("", "a", "Apfel", "Apefl", "Angle", "Bäume", "xyz", "Aachener")] needle: &str,
+    #[values(0, 1, 2, 3)] max_distance: usize,
+    #[values(EditDistance::Levenshtein, EditDistance::Damerau)] metric: EditDistance,
+) {
+    let haystack =
+        "\nA\nAachen\nAal\nAmpel\nAngel\nAngeln\nApfel\nAptel\nBaum\nBäume\nBäumen\nZaun\nyxz";
+    let ss = SortedString::new_checked(haystack, AsciiChar::LineFeed).unwrap();
+
+    let expected: Vec<_> = ss
+        .iter_with_ranges()
+        .map(|(range, entry)| (range, entry, edit_distance(entry, needle, metric)))
+        .filter(|(_, _, distance)| *distance <= max_distance)
+        .collect();
+
+    let matches: Vec<_> = ss
+        .fuzzy_search(needle, max_distance, metric)
+        .into_iter()
+        .map(|m| (m.range, m.entry, m.distance))
+        .collect();
+
+    assert_eq!(matches, expected);
+}
+
+#[rstest]
+#[case("Baume", EditDistance::Levenshtein, vec![("Baum", 1), ("Bäume", 1), ("Bäumen", 2)])]
+#[case("Bämue", EditDistance::Levenshtein, vec![("Bäume", 2)])]
+#[case("Bämue", EditDistance::Damerau, vec![("Bäume", 1), ("Bäumen", 2)])]
+fn test_fuzzy_search(
+    #[case] needle: &str,
+    #[case] metric: EditDistance,
+    #[case] expected: Vec<(&str, usize)>,
+) {
+    let ss = SortedString::new_checked("Apfel\nBaum\nBäume\nBäumen", AsciiChar::LineFeed).unwrap();
+
+    let matches: Vec<_> = ss
+        .fuzzy_search(needle, 2, metric)
+        .into_iter()
+        .map(|m| (m.entry, m.distance))
+        .collect();
+
+    assert_eq!(matches, expected);
+}
+
+#[test]
+fn test_fuzzy_search_terminates_on_unsorted_haystack() {
+    let ss = SortedString::new_unchecked("zzz,abc,zzy,abd,zz", AsciiChar::Comma);
+
+    let matches = ss.fuzzy_search("abc", 1, EditDistance::Levenshtein);
+    assert!(matches.iter().all(|m| m.distance <= 1));
+}
+
+#[rstest]
+#[case("a??le", "apple", true)]
+#[case("a??le", "aple", false)]
+#[case("re*ing", "reing", true)]
+#[case("re*ing", "rewriting", true)]
+#[case("re*ing", "rewritings", false)]
+#[case("*", "", true)]
+#[case("*a*b*", "xxaxxbxx", true)]
+#[case("*a*b*", "xxbxxaxx", false)]
+#[case("[bc]at", "cat", true)]
+#[case("[bc]at", "hat", false)]
+#[case("[!bc]at", "hat", true)]
+#[case("[^bc]at", "bat", false)]
+#[case("[a-c]", "b", true)]
+#[case("[a-c]", "d", false)]
+#[case("[a-]", "-", true)]
+#[case("[]]", "]", true)]
+// Escaped range bounds
+#[case(r"[\[-\]]", "]", true)]
+#[case(r"[\[-\]]", "\\", true)]
+#[case(r"[\[-\]]", "a", false)]
+#[case(r"[+-\]]", "A", true)]
+#[case(r"[+-\]]x", "]x", true)]
+#[case("\\*", "*", true)]
+#[case("\\*", "a", false)]
+#[case("B?ume", "Bäume", true)]
+#[case("[äöü]", "ö", true)]
+fn test_glob_is_match(#[case] pattern: &str, #[case] s: &str, #[case] expected: bool) {
+    assert_eq!(Glob::new(pattern).unwrap().is_match(s), expected);
+}
+
+#[rstest]
+#[case("[ab", GlobError::UnclosedClass { start: 0 })]
+#[case("x[a-", GlobError::UnclosedClass { start: 1 })]
+#[case(r"[a-\", GlobError::UnclosedClass { start: 0 })]
+#[case("[]", GlobError::UnclosedClass { start: 0 })]
+#[case("ab\\", GlobError::DanglingEscape)]
+fn test_glob_errors(#[case] pattern: &str, #[case] expected: GlobError) {
+    assert_eq!(Glob::new(pattern), Err(expected));
+}
+
+#[rstest]
+#[case("a??le", vec!["ample", "ankle", "apple"])]
+#[case("[bc]at", vec!["bat", "cat"])]
+#[case("*at", vec!["bat", "cat", "hat"])]
+#[case("re*", vec!["rewind", "rewriting"])]
+#[case("re*ing", vec!["rewriting"])]
+#[case("hat", vec!["hat"])]
+#[case("ha", vec![])]
+#[case("*", vec!["ample", "ankle", "apple", "bat", "cat", "hat", "rewind", "rewriting"])]
+fn test_entries_matching(#[case] pattern: &str, #[case] expected: Vec<&str>) {
+    let haystack = "ample\nankle\napple\nbat\ncat\nhat\nrewind\nrewriting";
+    let ss = SortedString::new_checked(haystack, AsciiChar::LineFeed).unwrap();
+    let glob = Glob::new(pattern).unwrap();
+
+    assert_eq!(ss.entries_matching(&glob).collect::<Vec<_>>(), expected);
+
+    let mut reversed = expected;
+    reversed.reverse();
+    assert_eq!(
+        ss.entries_matching(&glob).rev().collect::<Vec<_>>(),
+        reversed
+    );
+}
+
+#[test]
+fn test_entries_matching_case_insensitive_order() {
+    let haystack = "Apfel\napfel\nAmpel\nBaum";
+    let haystack = SortedString::sort_with_order(haystack, AsciiChar::LineFeed, &CaseInsensitive);
+    let ss = SortedString::new_checked_with_order(&haystack, AsciiChar::LineFeed, CaseInsensitive)
+        .unwrap();
+
+    // Narrowing is case-insensitive, matching is not.
+    let glob = Glob::new("ap*").unwrap();
+    assert_eq!(
+        ss.entries_matching(&glob).collect::<Vec<_>>(),
+        vec!["apfel"]
+    );
+}
+
+#[cfg(feature = "regex")]
+#[rstest]
+#[case("^Apfel", "Apfel")]
+#[case("^Ap(fel|ril)", "Ap")]
+#[case("^Bäume?$", "Bäum")]
+#[case("^(?i)apfel", "")]
+#[case("^a|^b", "")]
+#[case("Apfel", "")]
+#[case("(?m)^Apfel", "")]
+#[case("^.*x", "")]
+#[case("^", "")]
+fn test_regex_literal_prefix(#[case] pattern: &str, #[case] expected: &str) {
+    assert_eq!(RegexQuery::new(pattern).unwrap().literal_prefix(), expected);
+}
+
+#[cfg(feature = "regex")]
+#[rstest]
+#[case("^Ap", vec!["Apfel", "Aprikose", "April"])]
+#[case("^Apri(l|kosen?)$", vec!["Aprikose", "April"])]
+#[case("^(?i)ap", vec!["Apfel", "Aprikose", "April", "apfelsaft"])]
+#[case("el", vec!["Apfel", "Kapelle", "apfelsaft"])]
+#[case("^Bäume?$", vec!["Bäume"])]
+#[case("^Zaun", vec![])]
+#[case("", vec!["Apfel", "Aprikose", "April", "Baum", "Bäume", "Kapelle", "apfelsaft"])]
+fn test_entries_matching_regex(#[case] pattern: &str, #[case] expected: Vec<&str>) {
+    let haystack = "Apfel\nAprikose\nApril\nBaum\nBäume\nKapelle\napfelsaft";
+    let ss = SortedString::new_checked(haystack, AsciiChar::LineFeed).unwrap();
+    let query = RegexQuery::new(pattern).unwrap();
+
+    assert_eq!(
+        ss.entries_matching_regex(&query).collect::<Vec<_>>(),
+        expected
+    );
+
+    let mut reversed = expected;
+    reversed.reverse();
+    assert_eq!(
+        ss.entries_matching_regex(&query).rev().collect::<Vec<_>>(),
+        reversed
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_regex_query_invalid() {
+    assert!(RegexQuery::new("^(unclosed").is_err());
+}
+
+#[rstest]
+#[case("ung")]
+#[case("heit")]
+#[case("eit")]
+#[case("t")]
+#[case("ße")]
+#[case("e")]
+#[case("")]
+#[case("Zeitung")]
+#[case("xZeitung")]
+#[case("q")]
+fn test_entries_with_suffix_agrees_with_linear_scan(#[case] suffix: &str) {
+    let haystack = "Bahn\nFreiheit\nGesundheit\nHoffnung\nMaße\nStraße\nZeit\nZeitung\n";
+    let companion = SuffixSortedString::sort(haystack, AsciiChar::LineFeed);
+    let ss = SuffixSortedString::new_checked(&companion, AsciiChar::LineFeed).unwrap();
+
+    let mut expected: Vec<_> = haystack
+        .split('\n')
+        .filter(|e| e.ends_with(suffix))
+        .collect();
+    expected.sort_by(|a, b| a.chars().rev().cmp(b.chars().rev()));
+
+    assert_eq!(ss.entries_with_suffix(suffix).collect::<Vec<_>>(), expected);
+}
+
+#[rstest]
+#[case("Hoffnung\nZeitung\nBahn", true)]
+#[case("Bahn\nHoffnung\nZeitung", false)]
+#[case("Zeitung\nHoffnung", false)]
+fn test_suffix_sorted_string_validation(#[case] haystack: &str, #[case] sorted: bool) {
+    let ss = SuffixSortedString::new_checked(haystack, AsciiChar::LineFeed);
+
+    assert_eq!(ss.is_ok(), sorted);
+}
+
+#[test]
+fn test_suffix_sorted_string_search() {
+    let companion = SuffixSortedString::sort("Bahn,Hoffnung,Zeitung", AsciiChar::Comma);
+    assert_eq!(companion, "Hoffnung,Zeitung,Bahn");
+
+    let ss = SuffixSortedString::new_checked(&companion, AsciiChar::Comma).unwrap();
+    assert_eq!(ss.binary_search("Zeitung"), Ok(9..16));
+    assert_eq!(ss.as_sorted_string().search("Bahn").unwrap().entry, "Bahn");
+
+    let retrograde =
+        SortedString::new_checked_with_order(&companion, AsciiChar::Comma, Retrograde).unwrap();
+    assert_eq!(ss.as_sorted_string(), &retrograde);
+}
+
+#[rstest]
+#[case("rot")]
+#[case("Brot")]
+#[case("o")]
+#[case("äu")]
+#[case("ä")]
+#[case("e\nB")]
+#[case("\n")]
+#[case("")]
+#[case("Abendbrotbox")]
+#[case("x")]
+#[case("tb")]
+#[case(r"\")]
+#[case(r"a\")]
+#[case(r"\b")]
+#[case(",")]
+#[case(";")]
+#[case("b,")]
+#[case(",;,")]
+fn test_entries_containing_agrees_with_linear_scan(
+    #[case] needle: &str,
+    #[values(
+        ("\nAbendbrot\nBaum\nBrot\nBrot\nBrotbox\nBäume\nRotkohl\nTräume", AsciiChar::LineFeed.into(), Format::new()),
+        (r"a\\,a\b,c\,d", AsciiChar::Comma.into(), escaping()),
+        (r"a\\,;ab\,;,;b\x,;c\,;\,;", Separator::new(",;").unwrap(), escaping()),
+    )]
+    layout: (&str, Separator<'static>, Format),
+) {
+    let (haystack, sep, format) = layout;
+    let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format).unwrap();
+    let index = InfixIndex::new(&ss);
+
+    let expected: Vec<_> = ss
+        .iter_with_ranges()
+        .filter(|(_, entry)| ss.unescape(entry).contains(needle))
+        .collect();
+    let matches: Vec<_> = ss
+        .entries_containing(&index, needle)
+        .into_iter()
+        .map(|m| (m.range, m.entry))
+        .collect();
+
+    assert_eq!(matches, expected);
+}
+
+#[test]
+fn test_infix_index_roundtrip() {
+    let ss = SortedString::new_checked("Bäume,Traum", AsciiChar::Comma).unwrap();
+    let index = InfixIndex::new(&ss);
+    // One position per `char`, without separators
+    assert_eq!(index.len(), 10);
+
+    let bytes = index.as_bytes().to_vec();
+    let loaded = InfixIndex::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded, index);
+    assert_eq!(ss.entries_containing(&loaded, "um").len(), 2);
+
+    assert_eq!(InfixIndex::from_bytes(&bytes[1..]), None);
+    assert!(InfixIndex::from_bytes(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_infix_index_mismatch_does_not_panic() {
+    let large =
+        SortedString::new_checked("Abendbrot,Brot,Brotbox,Rotkohl", AsciiChar::Comma).unwrap();
+    let index = InfixIndex::new(&large);
+
+    let small = SortedString::new_checked("Bäume,Rot", AsciiChar::Comma).unwrap();
+    for needle in ["ot", "ä", "x", ""] {
+        let _ = small.entries_containing(&index, needle);
+    }
+
+    let garbage = InfixIndex::from_bytes(&[0xFF; 16]).unwrap();
+    assert!(small.entries_containing(&garbage, "a").is_empty());
+}
+
+#[rstest]
+#[case("Aachen\r\nAmpel\r\nApfel", "\r\n")]
+#[case("a||abc||b", "||")]
+#[case("Bäume·Hündin·Zäune", "·")]
+#[case("", "\r\n")]
+#[case("\r\n", "\r\n")]
+#[case("\r\n\r\na", "\r\n")]
+#[case("·a", "·")]
+fn test_string_separators_agree_with_split(#[case] haystack: &str, #[case] sep: &str) {
+    let ss = SortedString::new_unchecked_with_separator(
+        haystack,
+        Separator::new(sep).unwrap(),
+        Bytewise,
+    );
+    let expected = haystack.split(sep).collect::<Vec<_>>();
+
+    assert_eq!(ss.iter().collect::<Vec<_>>(), expected);
+    let mut reversed = ss.iter().rev().collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(reversed, expected);
+    assert!(ss.iter().size_hint().1.unwrap() >= expected.len());
+
+    for (range, entry) in ss.iter_with_ranges() {
+        assert_eq!(&haystack[range.clone()], entry);
+        assert_eq!(ss.binary_search(entry).map(|r| &haystack[r]), Ok(entry));
+    }
+
+    for needle in ["", "0", "a", "ab", "Ampel", "Apfelbaum", "Hund", "zzz", "Ä"] {
+        let expected_insertion_point = ss
+            .iter_with_ranges()
+            .find(|(_, entry)| *entry >= needle)
+            .map_or(haystack.len(), |(range, _)| range.start);
+
+        match ss.binary_search(needle) {
+            Ok(range) => assert_eq!(&haystack[range], needle),
+            Err(err) => assert_eq!(err.insertion_point, expected_insertion_point),
+        }
+        assert_eq!(
+            ss.entries_with_prefix(needle).collect::<Vec<_>>(),
+            expected
+                .iter()
+                .copied()
+                .filter(|entry| entry.starts_with(needle))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[rstest]
+#[case("c\r\nb\r\na", "\r\n", "a\r\nb\r\nc")]
+#[case("zz||a||m", "||", "a||m||zz")]
+#[case("Zäune·Bäume", "·", "Bäume·Zäune")]
+fn test_sort_with_string_separator(
+    #[case] haystack: &str,
+    #[case] sep: &str,
+    #[case] expected: &str,
+) {
+    let sep = Separator::new(sep).unwrap();
+    let sorted = SortedString::sort(haystack, sep);
+
+    assert_eq!(sorted, expected);
+    assert!(SortedString::new_checked(&sorted, sep).is_ok());
+    assert_eq!(
+        SortedString::new_checked(haystack, sep),
+        Err(b4s::SortedStringCreationError::NotSorted)
+    );
+}
+
+#[rstest]
+#[case("a|||b", "||")]
+#[case("b|||a", "||")]
+#[case("|||", "||")]
+#[case("a--b---", "--")]
+#[case("ababa", "aba")]
+fn test_overlapping_string_separator_is_rejected(#[case] haystack: &str, #[case] sep: &str) {
+    let sep = Separator::new(sep).unwrap();
+
+    assert_eq!(
+        SortedString::new_checked(haystack, sep),
+        Err(b4s::SortedStringCreationError::AmbiguousSeparator)
+    );
+
+    // Unchecked, entries are split the way `str::split` does.
+    let ss = SortedString::new_unchecked_with_separator(haystack, sep, Bytewise);
+    let entries: Vec<_> = haystack.split(sep.as_str()).collect();
+    assert_eq!(ss.iter().collect::<Vec<_>>(), entries);
+    assert_eq!(
+        SortedString::sort(haystack, sep),
+        entries.iter().sorted().join(sep.as_str())
+    );
+
+    // Results are meaningless, but searches neither panic nor hang.
+    for needle in ["", "a", "|b", "b", "z"] {
+        let _ = ss.binary_search(needle);
+        let _ = ss.entries_with_prefix(needle).count();
+        let _ = ss.entries_with_prefix(needle).rev().count();
+        let _ = ss.fuzzy_search(needle, 1, EditDistance::Levenshtein);
+    }
+}
+
+#[test]
+fn test_overlapping_separator_splitting_off_empty_entries_is_accepted() {
+    let sep = Separator::new("||").unwrap();
+
+    let ss = SortedString::new_checked("||||a||b", sep).unwrap();
+    assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["", "", "a", "b"]);
+    assert_eq!(ss.binary_search("a"), Ok(4..5));
+
+    let format = Format::new().skipping_empty_entries();
+    let ss = SortedString::new_checked_with_format("a||||b", sep, Bytewise, format).unwrap();
+    assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(ss.binary_search("b"), Ok(5..6));
+}
+
+#[test]
+fn test_sort_with_overlapping_separators() {
+    let sep = Separator::new("||").unwrap();
+    assert_eq!(SortedString::sort("b||||a", sep), "||a||b");
+    assert_eq!(SortedString::sort("b||||a||", sep), "||||a||b");
+    // Splits like `str::split`, even though the result is ambiguous.
+    assert_eq!(SortedString::sort("b|||a", sep), "b|||a");
+}
+
+#[rstest]
+fn test_overlapping_separators_terminate(
+    #[values("a||||b", "a|||b", "|||", "||", "b||||a||||", "||||||")] haystack: &str,
+    #[values(
+        Format::new(),
+        Format::new().with_trailing_separator(),
+        Format::new().skipping_empty_entries(),
+        Format::dictionary()
+    )]
+    format: Format,
+) {
+    let sep = Separator::new("||").unwrap();
+    let ss = SortedString::new_unchecked_with_format(haystack, sep, Bytewise, format);
+
+    let entries: Vec<_> = ss.iter().collect();
+    let mut reversed: Vec<_> = ss.iter().rev().collect();
+    reversed.reverse();
+    assert_eq!(reversed, entries);
+
+    for needle in entries.iter().copied().chain(["", "a", "|", "z"]) {
+        let _ = ss.binary_search(needle);
+        let _ = ss.entries_with_prefix(needle).count();
+    }
+
+    let sorted = SortedString::sort_with_format(haystack, sep, &Bytewise, format);
+    let resorted = SortedString::new_unchecked_with_format(&sorted, sep, Bytewise, format);
+    // Sorting ambiguous haystacks might join entries into further ambiguities.
+    if SortedString::new_checked_with_format(haystack, sep, Bytewise, format)
+        == Err(b4s::SortedStringCreationError::AmbiguousSeparator)
+    {
+        return;
+    }
+    assert_eq!(
+        resorted.iter().collect::<Vec<_>>(),
+        entries.into_iter().sorted().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_separator_creation() {
+    assert_eq!(Separator::new(""), None);
+    assert_eq!(Separator::new("\r\n"), Some(Separator::CRLF));
+    assert_eq!(Separator::from_bytes("·".as_bytes()), Separator::new("·"));
+    assert_eq!(Separator::from_bytes(&"·".as_bytes()[..1]), None);
+    assert_eq!(Separator::from(AsciiChar::Comma).as_str(), ",");
+    assert_eq!(Separator::CRLF.as_bytes(), b"\r\n");
+}
+
+#[test]
+fn test_separator_equality_is_by_bytes() {
+    let sep = Separator::new(",").unwrap();
+    assert_eq!(sep, Separator::from(AsciiChar::Comma));
+    assert_eq!(Separator::from_bytes(b","), Some(sep));
+    assert_eq!(sep.as_bytes(), b",");
+
+    assert_eq!(
+        SortedString::new_checked("a,b", sep).unwrap(),
+        SortedString::new_checked("a,b", AsciiChar::Comma).unwrap()
+    );
+}
+
+#[rstest]
+#[case(AsciiChar::Comma.into(), r#"SortedString("a,b", ',')"#)]
+#[case(Separator::new(",").unwrap(), r#"SortedString("a,b", ',')"#)]
+#[case(Separator::new("·").unwrap(), r#"SortedString("a·b", "·")"#)]
+#[case(Separator::CRLF, r#"SortedString("a\r\nb", "\r\n")"#)]
+fn test_sorted_string_display(#[case] sep: Separator<'static>, #[case] expected: &str) {
+    let haystack = ["a", "b"].join(sep.as_str());
+    let ss = SortedString::new_checked(&haystack, sep).unwrap();
+    assert_eq!(ss.to_string(), expected);
+}
+
+#[test]
+fn test_suffix_and_infix_with_string_separator() {
+    let sep = Separator::CRLF;
+    let companion = SuffixSortedString::sort("Hoffnung\r\nBahn\r\nZeitung", sep);
+    let ss = SuffixSortedString::new_checked(&companion, sep).unwrap();
+    assert_eq!(
+        ss.entries_with_suffix("ung").collect::<Vec<_>>(),
+        vec!["Hoffnung", "Zeitung"]
+    );
+
+    let ss = SortedString::new_checked("Bahn\r\nHoffnung\r\nZeitung", sep).unwrap();
+    let index = InfixIndex::new(&ss);
+    let matches: Vec<_> = ss
+        .entries_containing(&index, "n")
+        .into_iter()
+        .map(|m| m.entry)
+        .collect();
+    assert_eq!(matches, vec!["Bahn", "Hoffnung", "Zeitung"]);
+    assert!(ss.entries_containing(&index, "\r").is_empty());
+}
+
+/// Entries of `haystack` as laid out by `format`, the slow way.
+fn split_with_format<'a>(
+    haystack: &'a str,
+    sep: &str,
+    trailing_separator: bool,
+    skip_empty: bool,
+) -> Vec<(Range<usize>, &'a str)> {
+    let mut entries = Vec::new();
+    let mut start = 0;
+    for entry in haystack.split(sep) {
+        entries.push((start..start + entry.len(), entry));
+        start += entry.len() + sep.len();
+    }
+
+    if trailing_separator && haystack.ends_with(sep) {
+        entries.pop();
+    }
+    if skip_empty {
+        entries.retain(|(_, entry)| !entry.is_empty());
+    }
+
+    entries
+}
+
+#[rstest]
+#[case("a\nb\nc\n", "\n", true, false)]
+#[case("a\nb\nc", "\n", true, false)]
+#[case("\n", "\n", true, false)]
+#[case("\na\n", "\n", true, false)]
+#[case("a\r\nb\r\n", "\r\n", true, false)]
+#[case("a\n\nb\n\n\nc\n", "\n", false, true)]
+#[case("\n\na\nb", "\n", false, true)]
+#[case("\n\n\nAachen\n\nAmpel\nApfel\n\n", "\n", true, true)]
+#[case("a", "\n", false, true)]
+#[case("a\r\n\r\n\r\nb\r\n", "\r\n", true, true)]
+#[case("·a··b·c··", "·", false, true)]
+// Overlapping occurrences of the separator, splitting off empty entries.
+#[case("a||||b", "||", false, true)]
+#[case("||a||||||b||||", "||", true, true)]
+fn test_format_agrees_with_split(
+    #[case] haystack: &str,
+    #[case] sep: &str,
+    #[case] trailing_separator: bool,
+    #[case] skip_empty: bool,
+) {
+    let mut format = Format::new();
+    if trailing_separator {
+        format = format.with_trailing_separator();
+    }
+    if skip_empty {
+        format = format.skipping_empty_entries();
+    }
+    let sep = Separator::new(sep).unwrap();
+    let expected = split_with_format(haystack, sep.as_str(), trailing_separator, skip_empty);
+
+    let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format).unwrap();
+
+    assert_eq!(ss.iter_with_ranges().collect::<Vec<_>>(), expected);
+    let mut reversed = ss.iter_with_ranges().rev().collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(reversed, expected);
+    assert_eq!(
+        ss.iter().span(),
+        Some(expected[0].0.start..expected[expected.len() - 1].0.end)
+    );
+
+    for (range, entry) in &expected {
+        assert_eq!(ss.binary_search(entry), Ok(range.clone()));
+    }
+
+    for needle in [
+        "",
+        "0",
+        "a",
+        "aa",
+        "Ampel",
+        "Apfelbaum",
+        "b",
+        "bb",
+        "c",
+        "z",
+    ] {
+        let result = ss.binary_search(needle);
+        match expected.iter().find(|(_, entry)| *entry == needle) {
+            Some((range, _)) => assert_eq!(result, Ok(range.clone())),
+            None => {
+                let err = result.unwrap_err();
+                let mut before = expected.iter().filter(|(_, entry)| *entry < needle);
+                let after = expected.iter().find(|(_, entry)| *entry > needle);
+                assert!(before.all(|(range, _)| range.end <= err.insertion_point));
+                assert!(after.map_or(true, |(range, _)| err.insertion_point <= range.start));
+            }
+        }
+
+        let with_prefix: Vec<_> = expected
+            .iter()
+            .map(|(_, entry)| *entry)
+            .filter(|entry| entry.starts_with(needle))
+            .collect();
+        assert_eq!(
+            ss.entries_with_prefix(needle).collect::<Vec<_>>(),
+            with_prefix
+        );
+
+        let predecessor = expected.iter().rev().find(|(_, entry)| *entry < needle);
+        assert_eq!(ss.predecessor(needle), predecessor.cloned());
+        let successor = expected.iter().find(|(_, entry)| *entry > needle);
+        assert_eq!(ss.successor(needle), successor.cloned());
+
+        let fuzzy: Vec<_> = ss
+            .fuzzy_search(needle, 1, EditDistance::Levenshtein)
+            .into_iter()
+            .map(|m| m.entry)
+            .collect();
+        assert!(fuzzy.iter().all(|entry| !entry.is_empty() || !skip_empty));
+    }
+}
+
+#[rstest]
+#[case("", Format::new().with_trailing_separator())]
+#[case("\n", Format::new().skipping_empty_entries())]
+#[case("\n\n\n", Format::new().skipping_empty_entries().with_trailing_separator())]
+fn test_format_without_entries(#[case] haystack: &str, #[case] format: Format) {
+    assert_eq!(
+        SortedString::new_checked_with_format(haystack, AsciiChar::LineFeed, Bytewise, format),
+        Err(b4s::SortedStringCreationError::EmptyHaystack)
+    );
+
+    let ss = SortedString::new_unchecked_with_format(
+        haystack,
+        Separator::from_ascii(AsciiChar::LineFeed),
+        Bytewise,
+        format,
+    );
+    if !haystack.is_empty() {
+        assert_eq!(ss.iter().count(), 0);
+        assert_eq!(ss.iter().span(), None);
+        assert!(ss.binary_search("").is_err());
+        assert_eq!(ss.successor(""), None);
+        assert_eq!(ss.predecessor("z"), None);
+        assert!(ss.fuzzy_search("", 1, EditDistance::Levenshtein).is_empty());
+    }
+}
+
+#[rstest]
+#[case("b\na\n", Format::new().with_trailing_separator(), "a\nb\n")]
+#[case("b\n\na", Format::new().skipping_empty_entries(), "a\nb")]
+#[case("\nb\n\na\n", Format::new().skipping_empty_entries().with_trailing_separator(), "a\nb\n")]
+#[case("\n\n", Format::new().skipping_empty_entries().with_trailing_separator(), "")]
+#[case("b\na", Format::new(), "a\nb")]
+fn test_sort_with_format(#[case] haystack: &str, #[case] format: Format, #[case] expected: &str) {
+    let sep = AsciiChar::LineFeed;
+    let sorted = SortedString::sort_with_format(haystack, sep, &Bytewise, format);
+
+    assert_eq!(sorted, expected);
+    if !sorted.is_empty() {
+        assert!(SortedString::new_checked_with_format(&sorted, sep, Bytewise, format).is_ok());
+    }
+}
+
+#[test]
+fn test_format_with_reversed_order_skips_empty_entries_in_the_middle() {
+    let haystack = "c\n\nb\n\n\na\n";
+    let format = Format::new().skipping_empty_entries();
+    let ss = SortedString::new_checked_with_format(
+        haystack,
+        AsciiChar::LineFeed,
+        Reversed(Bytewise),
+        format,
+    )
+    .unwrap();
+
+    assert_eq!(ss.iter().collect::<Vec<_>>(), vec!["c", "b", "a"]);
+    assert_eq!(ss.binary_search("b"), Ok(Range { start: 3, end: 4 }));
+    assert!(ss.binary_search("").is_err());
+}
+
+#[rstest]
+#[case("# Header\nApfel\nBirne\n")]
+#[case("Apfel  \n\t Birne\t\n")]
+#[case("# Header\n\n  # indented comment\nApfel\n   \n# Stone fruit\nKirsche\n# Footer\n")]
+#[case("Apfel\r\nBirne\r\n# Windows\r\nKirsche\r\n")]
+#[case("#\n#\nApfel\n#\n#\nBirne\n#\n#")]
+#[case("Apfel\n Äpfel \n# ä\nÖl")]
+fn test_dictionary_format_agrees_with_cleaned_lines(#[case] haystack: &str) {
+    let sep = AsciiChar::LineFeed;
+    let format = Format::dictionary();
+    let expected: Vec<_> = haystack
+        .split('\n')
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format).unwrap();
+    assert_eq!(ss.iter().collect::<Vec<_>>(), expected);
+    assert_eq!(ss.iter().rev().count(), expected.len());
+
+    for (range, entry) in ss.iter_with_ranges() {
+        assert_eq!(&haystack[range.clone()], entry);
+        assert_eq!(ss.binary_search(entry), Ok(range));
+    }
+
+    for needle in [
+        "",
+        "#",
+        "# Header",
+        "Header",
+        "Apfel ",
+        "Apfe",
+        "Birne",
+        "Zwetschge",
+    ] {
+        assert_eq!(
+            ss.binary_search(needle).is_ok(),
+            expected.contains(&needle),
+            "{needle:?}"
+        );
+    }
+    assert_eq!(ss.entries_with_prefix("#").count(), 0);
+
+    let index = InfixIndex::new(&ss);
+    assert!(ss.entries_containing(&index, "#").is_empty());
+    assert!(ss.entries_containing(&index, " ").is_empty());
+
+    let cleaned = SortedString::sort_with_format(haystack, sep, &Bytewise, format);
+    assert_eq!(cleaned, expected.join("\n") + "\n");
+}
+
+#[test]
+fn test_dictionary_format_validates_cleaned_entries() {
+    let sep = AsciiChar::LineFeed;
+    let format = Format::dictionary();
+
+    // Sorted only once trimmed, and ignoring comments.
+    assert!(SortedString::new_checked_with_format(" b\na\n", sep, Bytewise, format).is_err());
+    assert!(SortedString::new_checked_with_format("a\n b\n", sep, Bytewise, format).is_ok());
+    assert!(SortedString::new_checked_with_format("b\n# a\nc", sep, Bytewise, format).is_ok());
+    assert_eq!(
+        SortedString::new_checked_with_format("# only\n  \n", sep, Bytewise, format),
+        Err(b4s::SortedStringCreationError::EmptyHaystack)
+    );
+
+    let unsorted = "# Header\nc\n b\n\na \n";
+    let sorted = SortedString::sort_with_format(unsorted, sep, &Bytewise, format);
+    assert_eq!(sorted, "a\nb\nc\n");
+}
+
+#[rstest]
+#[case(Format::new().with_comments(';'), "; comment\na\nb", vec!["a", "b"])]
+#[case(Format::new().with_comments(';'), " ; not a comment\na", vec![" ; not a comment", "a"])]
+#[case(Format::new().trimming_whitespace(), " a ,b", vec!["a", "b"])]
+#[case(Format::new().trimming_whitespace(), " , a", vec!["", "a"])]
+#[case(Format::new().trimming_whitespace().skipping_empty_entries(), " , a", vec!["a"])]
+fn test_format_options_in_isolation(
+    #[case] format: Format,
+    #[case] haystack: &str,
+    #[case] expected: Vec<&str>,
+) {
+    let sep = if haystack.contains(',') {
+        AsciiChar::Comma
+    } else {
+        AsciiChar::LineFeed
+    };
+    let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format).unwrap();
+
+    assert_eq!(ss.iter().collect::<Vec<_>>(), expected);
+    for entry in expected {
+        assert!(ss.binary_search(entry).is_ok());
+    }
+}
+
+#[rstest]
+#[case(b"Apfel\n\xC4pfel\n\xD6l", b'\n')]
+#[case(b"\x00\x01\xFF\x00\x02\xFF\xFE\xFE", 0xFF)]
+#[case(b"a", b',')]
+#[case(b",", b',')]
+#[case(b",a,\xE0\xE0,\xE0\xE1", b',')]
+fn test_sorted_bytes_agree_with_slice_search(#[case] haystack: &[u8], #[case] sep: u8) {
+    let sb = SortedBytes::new_checked(haystack, sep).unwrap();
+    let entries: Vec<&[u8]> = haystack.split(|byte| *byte == sep).collect();
+    assert_eq!(sb.iter().collect::<Vec<_>>(), entries);
+
+    let needles: [&[u8]; 8] = [
+        b"",
+        b"a",
+        b"Apfel",
+        b"\xC4pfel",
+        b"\x00\x02",
+        b"\xE0",
+        b"\xE0\xE1",
+        b"\xFF",
+    ];
+    for needle in entries.iter().copied().chain(needles) {
+        match sb.binary_search(needle) {
+            Ok(range) => assert_eq!(&haystack[range], needle),
+            Err(err) => {
+                assert!(!entries.contains(&needle));
+                let expected = entries
+                    .iter()
+                    .position(|entry| *entry > needle)
+                    .map_or(haystack.len(), |i| {
+                        entries[..i].iter().map(|entry| entry.len() + 1).sum()
+                    });
+                assert_eq!(err.insertion_point, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sorted_bytes_creation_and_sorting() {
+    assert_eq!(
+        SortedBytes::new_checked(b"", b','),
+        Err(b4s::SortedStringCreationError::EmptyHaystack)
+    );
+    assert_eq!(
+        SortedBytes::new_checked(b"\xFF,\x00", b','),
+        Err(b4s::SortedStringCreationError::NotSorted)
+    );
+
+    let sorted = SortedBytes::sort(b"\xFF,\x00,\x80", b',');
+    assert_eq!(sorted, b"\x00,\x80,\xFF");
+    assert!(SortedBytes::new_checked(&sorted, b',').is_ok());
+
+    let sb = SortedBytes::new_unchecked(b"a\xFFb", 0xFF);
+    assert_eq!(sb.to_string(), r#"SortedBytes("a\xffb", '\xff')"#);
+    assert_eq!(sb.binary_search("b"), Ok(Range { start: 2, end: 3 }));
+}
+
+#[rstest]
+#[case("Apfel,Äpfel".as_bytes(), b',', true)]
+#[case(b"Apfel,\xC4pfel", b',', false)]
+// Valid UTF-8 ("ä"), but the separator is a continuation byte.
+#[case("a,ä".as_bytes(), 0xA4, false)]
+#[case("a\r\nb".as_bytes(), b'\n', true)]
+// Empty entries
+#[case(b",", b',', true)]
+#[case(b",a", b',', true)]
+#[case(b",,,", b',', true)]
+#[case(b",,a,b,c", b',', true)]
+fn test_sorted_string_from_utf8(#[case] haystack: &[u8], #[case] sep: u8, #[case] valid: bool) {
+    let sb = SortedBytes::new_unchecked(haystack, sep);
+    let ss = SortedString::from_utf8(sb);
+
+    assert_eq!(ss.is_ok(), valid);
+    if let Ok(ss) = ss {
+        let entries: Vec<_> = ss.iter().map(str::as_bytes).collect();
+        assert_eq!(entries, sb.iter().collect::<Vec<_>>());
+
+        let misses = ["", "0", "A", "Apfelmus", "aa", "b", "z", "Ü"];
+        let needles = sb
+            .iter()
+            .map(|entry| std::str::from_utf8(entry).unwrap())
+            .chain(misses);
+
+        // Compares hits as well as misses, down to their `SearchError`.
+        for needle in needles {
+            assert_eq!(
+                ss.binary_search(needle),
+                sb.binary_search(needle),
+                "{needle:?}"
+            );
+        }
+    }
+}
+
+fn escaping() -> Format {
+    Format::new()
+        .with_escape(AsciiChar::BackSlash)
+        .with_trailing_separator()
+}
+
+#[rstest]
+#[case(r"Hello,Hello\, World,Tschüss", AsciiChar::Comma.into(), vec!["Hello", "Hello, World", "Tschüss"])]
+// An escaped escape ends the entry.
+#[case(r"a\\,b", AsciiChar::Comma.into(), vec![r"a\", "b"])]
+#[case(r"a\\\,b", AsciiChar::Comma.into(), vec![r"a\,b"])]
+// Escapes not followed by a separator or escape are taken literally.
+#[case(r"x\y,z", AsciiChar::Comma.into(), vec![r"x\y", "z"])]
+#[case(r"a\||b||c", Separator::new("||").unwrap(), vec!["a||b", "c"])]
+#[case(r"a,b,", AsciiChar::Comma.into(), vec!["a", "b"])]
+// An escaped trailing separator is not trailing.
+#[case(r"a,b\,", AsciiChar::Comma.into(), vec!["a", "b,"])]
+#[case(r"\,", AsciiChar::Comma.into(), vec![","])]
+fn test_escaped_separators_agree_with_unescaped_entries(
+    #[case] haystack: &str,
+    #[case] sep: Separator<'static>,
+    #[case] expected: Vec<&str>,
+) {
+    let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, escaping()).unwrap();
+
+    let entries: Vec<_> = ss.iter_with_ranges().collect();
+    let unescaped: Vec<_> = entries
+        .iter()
+        .map(|(_, entry)| ss.unescape(entry))
+        .collect();
+    assert_eq!(unescaped, expected);
+
+    for ((range, entry), needle) in entries.iter().zip(&expected) {
+        assert_eq!(&haystack[range.clone()], *entry);
+        assert_eq!(ss.binary_search(needle), Ok(range.clone()));
+        assert_eq!(ss.search(needle).map(|found| found.entry), Ok(*entry));
+    }
+
+    for needle in ["", "a,", "Hello", r"Hello\, World", "zzz"] {
+        if !expected.contains(&needle) {
+            assert!(ss.binary_search(needle).is_err(), "{needle:?}");
+        }
+    }
+}
+
+#[rstest]
+#[case("a\\\n  \nb\n", AsciiChar::LineFeed.into(), vec!["a\n", "b"])]
+#[case("\\\n \n  a\\\n\n", AsciiChar::LineFeed.into(), vec!["\n", "a\n"])]
+#[case("a\\\r\n \r\nb", Separator::CRLF, vec!["a\r\n", "b"])]
+fn test_escaped_whitespace_separator_survives_trimming(
+    #[case] haystack: &str,
+    #[case] sep: Separator<'static>,
+    #[case] expected: Vec<&str>,
+) {
+    let format = Format::dictionary().with_escape(AsciiChar::BackSlash);
+    let ss = SortedString::new_checked_with_format(haystack, sep, Bytewise, format).unwrap();
+
+    let entries: Vec<_> = ss.iter().map(|entry| ss.unescape(entry)).collect();
+    assert_eq!(entries, expected);
+
+    for (range, entry) in ss.iter_with_ranges() {
+        assert!(entry.ends_with(sep.as_str()) || !entry.ends_with('\\'));
+        assert_eq!(ss.binary_search(ss.unescape(entry)), Ok(range));
+    }
+}
+
+#[test]
+fn test_escaped_entries_sort_by_unescaped_content() {
+    let sep = AsciiChar::Comma;
+    let format = escaping();
+
+    // Unescaped, "a,b" comes before "a-b", but the raw "a\,b" comes after.
+    assert!(SortedString::new_checked_with_format(r"a\,b,a-b", sep, Bytewise, format).is_ok());
+    assert_eq!(
+        SortedString::new_checked_with_format(r"a-b,a\,b", sep, Bytewise, format),
+        Err(b4s::SortedStringCreationError::NotSorted)
+    );
+    assert_eq!(
+        SortedString::sort_with_format(r"a-b,a\,b,a\\", sep, &Bytewise, format),
+        r"a\,b,a-b,a\\,"
+    );
+}
+
+#[rstest]
+#[case(Format::new(), r"a\\,b")]
+#[case(Format::new().with_trailing_separator(), r"a\\,b,")]
+fn test_sorting_escapes_entries_ending_in_escape(#[case] format: Format, #[case] expected: &str) {
+    let sep = AsciiChar::Comma;
+    let format = format.with_escape(AsciiChar::BackSlash);
+
+    let sorted = SortedString::sort_with_format(r"b,a\", sep, &Bytewise, format);
+    assert_eq!(sorted, expected);
+    assert_eq!(
+        SortedString::sort_with_format(&sorted, sep, &Bytewise, format),
+        sorted
+    );
+
+    let ss = SortedString::new_checked_with_format(&sorted, sep, Bytewise, format).unwrap();
+    let entries: Vec<_> = ss.iter().map(|entry| ss.unescape(entry)).collect();
+    assert_eq!(entries, vec![r"a\", "b"]);
+}
+
+#[rstest]
+#[case("a,,b", AsciiChar::Comma.into(), AsciiChar::Comma)]
+#[case("a", Separator::new(",\\").unwrap(), AsciiChar::BackSlash)]
+fn test_escape_within_separator_rejected(
+    #[case] haystack: &str,
+    #[case] sep: Separator<'static>,
+    #[case] escape: AsciiChar,
+) {
+    let format = Format::new().with_escape(escape);
+    assert_eq!(
+        SortedString::new_checked_with_format(haystack, sep, Bytewise, format),
+        Err(b4s::SortedStringCreationError::EscapeInSeparator)
+    );
+}
+
+#[test]
+fn test_escaped_entries_in_queries() {
+    let haystack = r"a\,b,a-b,c\\";
+    let ss =
+        SortedString::new_checked_with_format(haystack, AsciiChar::Comma, Bytewise, escaping())
+            .unwrap();
+
+    let prefixed: Vec<_> = ss.entries_with_prefix("a,").collect();
+    assert_eq!(prefixed, vec![r"a\,b"]);
+    assert_eq!(ss.lower_bound("a-"), 5);
+    assert_eq!(
+        ss.successor("a,b"),
+        Some((Range { start: 5, end: 8 }, "a-b"))
+    );
+    assert_eq!(
+        ss.predecessor("a-b"),
+        Some((Range { start: 0, end: 4 }, r"a\,b"))
+    );
+
+    let glob = Glob::new("a[,]*").unwrap();
+    assert_eq!(
+        ss.entries_matching(&glob).collect::<Vec<_>>(),
+        vec![r"a\,b"]
+    );
+    let glob = Glob::new(r"*\\").unwrap();
+    assert_eq!(ss.entries_matching(&glob).collect::<Vec<_>>(), vec![r"c\\"]);
+
+    let index = InfixIndex::new(&ss);
+    let containing = |needle| -> Vec<&str> {
+        ss.entries_containing(&index, needle)
+            .iter()
+            .map(|m| m.entry)
+            .collect()
+    };
+    assert_eq!(containing(",b"), vec![r"a\,b"]);
+    assert_eq!(containing(r"c\"), vec![r"c\\"]);
+    assert_eq!(containing("b"), vec![r"a\,b", "a-b"]);
+
+    let fuzzy: Vec<_> = ss
+        .fuzzy_search("a,c", 1, EditDistance::Levenshtein)
+        .into_iter()
+        .map(|m| (m.entry, m.distance))
+        .collect();
+    assert_eq!(fuzzy, vec![(r"a\,b", 1)]);
+}